@@ -5,35 +5,35 @@ use std::fs;
 use parseal::{parsing::{tokens::{Less, Greater, Equal, ForwardSlash}, Parse, self, Identifier, StringValue, charstream::CharStream}, Parsable};
 
 #[derive(Parsable, Debug, Clone)]
-pub struct HTML {
-    parts: Vec<Scope>
+pub struct HTML<'a> {
+    parts: Vec<Scope<'a>>
 }
 
 #[derive(Parsable, Debug, Clone)]
-pub struct LabelArg(Identifier, Equal, StringValue);
+pub struct LabelArg<'a>(Identifier<'a>, Equal, StringValue<'a>);
 
 #[derive(Parsable, Debug, Clone)]
-pub struct LabelArgs {
-    args: Vec<LabelArg>
+pub struct LabelArgs<'a> {
+    args: Vec<LabelArg<'a>>
 }
 
 #[derive(Parsable, Debug, Clone)]
-pub struct StartLabel(Less, Identifier, Vec<LabelArgs>, Greater);
+pub struct StartLabel<'a>(Less, Identifier<'a>, Vec<LabelArgs<'a>>, Greater);
 
 #[derive(Parsable, Debug, Clone)]
-pub struct EndLabel(Less, ForwardSlash, Identifier, Greater);
+pub struct EndLabel<'a>(Less, ForwardSlash, Identifier<'a>, Greater);
 
 #[derive(Parsable, Debug, Clone)]
-pub struct Scope {
-    start: StartLabel,
-    end: EndLabel
+pub struct Scope<'a> {
+    start: StartLabel<'a>,
+    end: EndLabel<'a>
 }
 
 fn main() {
     let file = fs::read_to_string("examples/html/example.html")
         .expect("Expected example file to exists.");
 
-    let mut buffer = CharStream::new(file).build();
+    let mut buffer = CharStream::new(&file).build();
 
     HTML::parse(&mut buffer).unwrap();
 }
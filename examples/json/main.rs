@@ -2,46 +2,60 @@
 
 use std::fs;
 
-use parseal::{parsing::{self, Group, List, tokens::{Bracket, Comma, Brace, Colon}, Number, StringValue, Parse, charstream::CharStream, Identifier}, Parsable};
+use parseal::{parsing::{self, Group, List, tokens::{Bracket, Comma, Brace, Colon}, Number, StringValue, Parse, charstream::CharStream, Identifier, print::Printer}, Parsable, Printable};
 
-#[derive(Debug, Parsable, Clone)]
-pub struct JSONList {
+#[derive(Debug, Parsable, Printable, Clone)]
+pub struct JSONList<'a> {
 	list: Group<Bracket,
-		List<JSONNode, Comma>>
+		List<JSONNode<'a>, Comma>>
 }
 
-#[derive(Debug, Parsable, Clone)]
-pub struct NamedValue {
-	name: StringValue,
+#[derive(Debug, Parsable, Printable, Clone)]
+pub struct NamedValue<'a> {
+	name: StringValue<'a>,
 	colon: Colon,
-	value: JSONNode
+	value: JSONNode<'a>
 }
 
-#[derive(Debug, Parsable, Clone)]
-pub struct JSONObject {
+#[derive(Debug, Parsable, Printable, Clone)]
+pub struct JSONObject<'a> {
 	map: Group<Brace,
-		List<NamedValue, Comma>>
+		List<NamedValue<'a>, Comma>>
 }
 
-#[derive(Debug, Parsable, Clone)]
-pub enum Value {
-	String(StringValue),
-	Number(Number),
-	Bool(Identifier)
+#[derive(Debug, Parsable, Printable, Clone)]
+pub enum Value<'a> {
+	String(StringValue<'a>),
+	Number(Number<'a>),
+	Bool(Identifier<'a>)
 }
 
-#[derive(Debug, Parsable, Clone)]
-pub enum JSONNode {
-	List(JSONList),
-	Object(JSONObject),
-	Value(Value)
+#[derive(Debug, Parsable, Printable, Clone)]
+pub enum JSONNode<'a> {
+	List(JSONList<'a>),
+	Object(JSONObject<'a>),
+	Value(Value<'a>)
+}
+
+/// Counts the values in a node, including the lists and objects themselves.
+fn count(node: &JSONNode) -> usize {
+	match node {
+		JSONNode::List(list) => 1 + list.list.inner().map_or(0, |items| items.iter().map(count).sum()),
+		JSONNode::Object(object) => 1 + object.map.inner().map_or(0, |values| values.iter().map(|named| count(&named.value)).sum()),
+		JSONNode::Value(_) => 1
+	}
 }
 
 fn main() {
 	let file = fs::read_to_string("examples/json/example.json")
 		.expect("Expected example file to exist.");
 
-	let mut buffer = CharStream::new(file).build();
+	let mut buffer = CharStream::new(&file).build();
 	let value = JSONNode::parse(&mut buffer);
 	println!("value: {:#?}", value);
+
+	if let Ok(value) = value {
+		println!("values: {}", count(&value));
+		println!("formatted:\n{}", Printer::new().indent(2).print(&value));
+	}
 }
@@ -1,41 +1,45 @@
 use std::fs;
 
-use parseal::{parsing::{self, charstream::CharStream, Parse, StringValue, Number, Identifier, tokens, Indent}, Parsable};
-
-#[derive(Parsable, Debug, Clone)]
-pub enum NamedValue {
-	Object(ObjectValue),
-	String(StringValue),
-	Number(Number),
-	Bool(Identifier)
+use parseal::{parsing::{self, charstream::CharStream, Parse, StringValue, Number, Identifier, tokens, Indent, print::Printer}, Parsable, Printable};
+
+#[derive(Parsable, Printable, Debug, Clone)]
+pub enum NamedValue<'a> {
+	Object(ObjectValue<'a>),
+	String(StringValue<'a>),
+	Number(Number<'a>),
+	Bool(Identifier<'a>)
 }
 
-#[derive(Parsable, Debug, Clone)]
-pub enum ObjectValue {
-	Object(Indent<(Identifier, tokens::Colon, NamedValue)>),
-	List(Indent<(tokens::Hyphen, Value)>)
+#[derive(Parsable, Printable, Debug, Clone)]
+pub enum ObjectValue<'a> {
+	Object(Indent<(Identifier<'a>, tokens::Colon, NamedValue<'a>)>),
+	List(Indent<(tokens::Hyphen, Value<'a>)>)
 }
 
-#[derive(Parsable, Debug, Clone)]
-pub enum Value {
-	Named(Identifier, tokens::Colon, ObjectValue),
-	String(StringValue),
-	Number(Number),
-	Bool(#[value("true", "false")] Identifier)
+#[derive(Parsable, Printable, Debug, Clone)]
+pub enum Value<'a> {
+	Named(Identifier<'a>, tokens::Colon, ObjectValue<'a>),
+	String(StringValue<'a>),
+	Number(Number<'a>),
+	Bool(#[value("true", "false")] Identifier<'a>)
 }
 
-#[derive(Parsable, Debug, Clone)]
-pub struct YAML {
+#[derive(Parsable, Printable, Debug, Clone)]
+pub struct YAML<'a> {
 	#[whitespace(KeepAll)]
 	start: [tokens::Hyphen; 3],
-	value: Value
+	value: ObjectValue<'a>
 }
 
 pub fn main () {
 	let file = fs::read_to_string("examples/yaml/example.yaml").unwrap();
 
-	let mut charstream = CharStream::new(file).build();
+	let mut charstream = CharStream::new(&file).build();
 	let value = YAML::parse(&mut charstream);
 
 	println!("value: {:#?}", value);
+
+	if let Ok(value) = value {
+		println!("formatted:\n{}", Printer::new().indent(2).print(&value));
+	}
 }
\ No newline at end of file
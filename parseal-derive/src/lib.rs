@@ -1,25 +1,107 @@
 use proc_macro::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{DeriveInput, parse_macro_input, Data, Error, spanned::Spanned, DataStruct, DataEnum, Ident, Fields, Field, Attribute, Meta, MetaList, Index};
+use syn::{DeriveInput, parse_macro_input, Data, Error, spanned::Spanned, DataStruct, DataEnum, Ident, Fields, Field, Attribute, Meta, MetaList, Index, NestedMeta, Lit, Generics, GenericParam, Lifetime, LifetimeDef, parse_quote};
 
 
-#[proc_macro_derive(Parsable, attributes(whitespace, value))]
+#[proc_macro_derive(Parsable, attributes(whitespace, value, context))]
 pub fn parsable_fn(item: TokenStream) -> TokenStream {
     let item = parse_macro_input!(item as DeriveInput);
     match &item.data {
-        Data::Struct(value) => derive_struct(&item.ident, value),
-        Data::Enum(value) => derive_enum(&item.ident, value),
+        Data::Struct(value) => derive_struct(&item.ident, &item.generics, value, &item.attrs),
+        Data::Enum(value) => derive_enum(&item.ident, &item.generics, value, &item.attrs),
         Data::Union(_) => TokenStream::from(Error::new(item.span(), "Can not derive Parse from a union type.").to_compile_error())
     }
 }
 
-fn derive_struct(ident: &Ident, value: &DataStruct) -> TokenStream {
+#[proc_macro_derive(Printable, attributes(whitespace))]
+pub fn printable_fn(item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as DeriveInput);
+    let ident = &item.ident;
+    let body = match &item.data {
+        Data::Struct(value) => {
+            let (pattern, prints) = print_fields(quote! { Self }, &value.fields);
+            quote! {
+                let #pattern = self;
+                #(#prints)*
+            }
+        }
+        Data::Enum(value) => {
+            let variants = value.variants.iter().map(|variant| {
+                let variant_ident = &variant.ident;
+                let (pattern, prints) = print_fields(quote! { Self::#variant_ident }, &variant.fields);
+                quote! {
+                    #pattern => {
+                        #(#prints)*
+                    }
+                }
+            });
+            quote! {
+                match self {
+                    #(#variants)*
+                }
+            }
+        }
+        Data::Union(_) => return TokenStream::from(Error::new(item.span(), "Can not derive Print from a union type.").to_compile_error())
+    };
+
+    let mut generics = item.generics.clone();
+    let types = generics.type_params().map(|param| param.ident.clone()).collect::<Vec<_>>();
+    let where_clause = generics.make_where_clause();
+    for ty in types {
+        where_clause.predicates.push(parse_quote! { #ty: parsing::print::Print });
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics parsing::print::Print for #ident #ty_generics #where_clause {
+            fn print(&self, printer: &mut parsing::print::Printer) {
+                #body
+            }
+        }
+    }.into()
+}
+
+/// Returns the pattern that binds the fields of a struct or variant, and the statements that print them in order.
+fn print_fields(path: quote::__private::TokenStream, fields: &Fields) -> (quote::__private::TokenStream, Vec<quote::__private::TokenStream>) {
+    let inner = fields.iter().enumerate().map(|(i, field)| inner_ident(&field.ident, i)).collect::<Vec<_>>();
+    let pattern = match fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote! { #path { #(#names: #inner),* } }
+        }
+        Fields::Unnamed(_) => quote! { #path(#(#inner),*) },
+        Fields::Unit => quote! { #path }
+    };
+    let prints = fields.iter().zip(&inner).map(|(field, ident)| {
+        let keep_all = get_attr(&field.attrs, "whitespace")
+            .is_some_and(|attr| attr.nested.iter().any(|meta| meta.to_token_stream().to_string() == "KeepAll"));
+        match keep_all {
+            true => quote! {
+                printer.tight(|printer| parsing::print::Print::print(#ident, printer));
+            },
+            false => quote! {
+                parsing::print::Print::print(#ident, printer);
+            }
+        }
+    }).collect();
+
+    (pattern, prints)
+}
+
+fn derive_struct(ident: &Ident, generics: &Generics, value: &DataStruct, attrs: &[Attribute]) -> TokenStream {
     let fields = value.fields.iter().collect::<Vec<_>>();
-    let definitions = derive_fields(fields.clone());
+    let type_name = match get_context(attrs) {
+        Ok(label) => label.unwrap_or_else(|| ident.to_string()),
+        Err(error) => return error
+    };
+    let definitions = match derive_fields(fields.clone(), &type_name) {
+        Ok(definitions) => definitions,
+        Err(error) => return error
+    };
     let parse_result = match &value.fields {
         Fields::Named(fields) => {
             let fields = fields.named.iter().map(|field| &field.ident);
-            let inner_fields = fields.clone().enumerate().map(|(i, field)| inner_ident(&field, i));
+            let inner_fields = fields.clone().enumerate().map(|(i, field)| inner_ident(field, i));
             quote! {
                 ::std::result::Result::Ok(Self {
                     #(#fields: #inner_fields),*
@@ -34,29 +116,40 @@ fn derive_struct(ident: &Ident, value: &DataStruct) -> TokenStream {
         }
         Fields::Unit => return TokenStream::from(Error::new(ident.span(), "Can not derive trait Parse for a unit struct.").to_compile_error())
     };
-    let first_ident = get_ident(&fields.first().unwrap().ident, 0);
-    let last_ident = get_ident(&fields.last().unwrap().ident, fields.len() - 1);
+    let field_idents = fields.iter().enumerate().map(|(i, field)| get_ident(&field.ident, i));
+    let (parse_generics, lifetime) = parse_generics(ident, generics);
+    let (impl_generics, _, where_clause) = parse_generics.split_for_impl();
+    let (_, ty_generics, _) = generics.split_for_impl();
     quote! {
-        impl Parse for #ident {
-            fn parse(value: &mut parsing::charstream::CharStream) -> ::std::result::Result<Self, parsing::ParseError> {
-                #(#definitions)*
-                #parse_result
+        impl #impl_generics Parse<#lifetime> for #ident #ty_generics #where_clause {
+            fn parse(value: &mut parsing::charstream::CharStream<#lifetime>) -> ::std::result::Result<Self, parsing::ParseError> {
+                value.attempt(|value| {
+                    #(#definitions)*
+                    #parse_result
+                })
             }
 
             fn span(&self) -> parsing::charstream::Span {
-                parsing::charstream::Span::new(self.#first_ident.span().start, self.#last_ident.span().end)
+                parsing::charstream::Span::join(&[#(self.#field_idents.span()),*])
             }
         }
     }.into()
 }
 
-fn derive_enum(ident: &Ident, value: &DataEnum) -> TokenStream {
+fn derive_enum(ident: &Ident, generics: &Generics, value: &DataEnum, attrs: &[Attribute]) -> TokenStream {
+    let type_name = match get_context(attrs) {
+        Ok(label) => label.unwrap_or_else(|| ident.to_string()),
+        Err(error) => return error
+    };
+    let (parse_generics, lifetime) = parse_generics(ident, generics);
+    let (impl_generics, _, where_clause) = parse_generics.split_for_impl();
+    let (_, ty_generics, _) = generics.split_for_impl();
     let variants = value.variants.iter().map(|variant| {
         let ident = Ident::new(&format!("__parse_{}", variant.ident.to_string().to_lowercase()), variant.span());
         (&variant.ident, ident, &variant.fields, &variant.attrs)
     });
     let variant_functions = match variants.clone()
-        .map(|(field_ident, func_ident, fields, attrs)| derive_variant_function(field_ident, func_ident, fields, attrs))
+        .map(|(field_ident, func_ident, fields, attrs)| derive_variant_function(&type_name, &lifetime, field_ident, func_ident, fields, attrs))
         .collect::<Result<Vec<_>,_>>() {
             Ok(value) => value,
             Err(error) => return error
@@ -64,13 +157,12 @@ fn derive_enum(ident: &Ident, value: &DataEnum) -> TokenStream {
 
     let parse_variants = variants.clone().map(|(_, func_ident, _, _)| {
         quote! {
-            let mut __value = value.clone();
-            match Self::#func_ident(&mut __value) {
-                ::std::result::Result::Ok(inner) => {
-                    value.goto(__value.position())?;
-                    options.push(inner);
-                }
-                ::std::result::Result::Err(err) => error = ::std::option::Option::Some(err)
+            match value.attempt(Self::#func_ident) {
+                ::std::result::Result::Ok(inner) => return ::std::result::Result::Ok(inner),
+                ::std::result::Result::Err(err) => error = ::std::option::Option::Some(match error.take() {
+                    ::std::option::Option::Some(error) => parsing::ParseError::merge(error, err),
+                    ::std::option::Option::None => err
+                })
             }
         }
     });
@@ -90,27 +182,28 @@ fn derive_enum(ident: &Ident, value: &DataEnum) -> TokenStream {
             }
         });
 
-        let first = inner_ident(&fields.first().unwrap().ident, 0);
-        let last = inner_ident(&fields.last().unwrap().ident, fields.len() - 1);
+        let inner = fields.iter().enumerate().map(|(i, field)| inner_ident(&field.ident, i));
+        let pattern = match fields.first().unwrap().ident {
+            Some(_) => quote! { Self::#variant_ident { #(#definitions),* } },
+            None => quote! { Self::#variant_ident(#(#definitions),*) }
+        };
 
         quote! {
-            Self::#variant_ident(#(#definitions),*) => 
-                parsing::charstream::Span::new(#first.span().start, #last.span().end),
+            #pattern => 
+                parsing::charstream::Span::join(&[#(#inner.span()),*]),
         }
     });
 
     quote! {
-        impl #ident {
+        impl #impl_generics #ident #ty_generics #where_clause {
             #(#variant_functions)*
         }
 
-        impl Parse for #ident {
-            fn parse(value: &mut parsing::charstream::CharStream) -> ::std::result::Result<Self, parsing::ParseError> {
-                let mut options = Vec::new();
-                let mut error = None;
+        impl #impl_generics Parse<#lifetime> for #ident #ty_generics #where_clause {
+            fn parse(value: &mut parsing::charstream::CharStream<#lifetime>) -> ::std::result::Result<Self, parsing::ParseError> {
+                let mut error: ::std::option::Option<parsing::ParseError> = ::std::option::Option::None;
                 #(#parse_variants)*
-                options.sort_by(|a, b| a.span().partial_cmp(&b.span()).unwrap());
-                options.first().and_then(|option| Some(option.clone())).ok_or(error.unwrap())
+                ::std::result::Result::Err(error.unwrap())
             }
 
             fn span(&self) -> parsing::charstream::Span {
@@ -122,12 +215,13 @@ fn derive_enum(ident: &Ident, value: &DataEnum) -> TokenStream {
     }.into()
 }
 
-fn derive_variant_function(field_ident: &Ident, func_ident: Ident, fields: &Fields, _attrs: &[Attribute]) -> Result<quote::__private::TokenStream, TokenStream> {
-    let definitions = derive_fields(fields.iter().collect());
+fn derive_variant_function(type_name: &str, lifetime: &Lifetime, field_ident: &Ident, func_ident: Ident, fields: &Fields, attrs: &[Attribute]) -> Result<quote::__private::TokenStream, TokenStream> {
+    let variant_name = get_context(attrs)?.unwrap_or_else(|| format!("{}::{}", type_name, field_ident));
+    let definitions = derive_fields(fields.iter().collect(), &variant_name)?;
     let parse_result = match fields {
         Fields::Named(fields) => {
             let fields = fields.named.iter().map(|field| &field.ident);
-            let inner_fields = fields.clone().enumerate().map(|(i, field)| inner_ident(&field, i));
+            let inner_fields = fields.clone().enumerate().map(|(i, field)| inner_ident(field, i));
             quote! {
                 ::std::result::Result::Ok(Self::#field_ident {
                     #(#fields: #inner_fields),*
@@ -143,47 +237,66 @@ fn derive_variant_function(field_ident: &Ident, func_ident: Ident, fields: &Fiel
         Fields::Unit => return Err(TokenStream::from(Error::new(field_ident.span(), "Can not derive trait Parse for a unit variant.").to_compile_error()))
     };
     Ok(quote! {
-        fn #func_ident(value: &mut parsing::charstream::CharStream) -> ::std::result::Result<Self, parsing::ParseError> {
+        fn #func_ident(value: &mut parsing::charstream::CharStream<#lifetime>) -> ::std::result::Result<Self, parsing::ParseError> {
             #(#definitions)*
             #parse_result
         }
     })
 }
 
-fn derive_fields(fields: Vec<&Field>) -> Vec<quote::__private::TokenStream> {
+fn derive_fields(fields: Vec<&Field>, type_name: &str) -> Result<Vec<quote::__private::TokenStream>, TokenStream> {
     let fields = fields.iter().enumerate().map(|(i, field)| {
-        let field = field.clone().clone();
-        (inner_ident(&field.ident, i), field.ty, field.attrs)
+        let field = (*field).clone();
+        let field_name = get_ident(&field.ident, i).to_string();
+        (inner_ident(&field.ident, i), field_name, field.ty, field.attrs)
     });
-    fields.map(|(ident, ty, attrs)| {
+    fields.map(|(ident, field_name, ty, attrs)| {
         let whitespace_attr = get_attr(&attrs, "whitespace");
         let value_attr = get_attr(&attrs, "value");
+        let context = match get_context(&attrs)? {
+            Some(label) => quote! { parsing::Context::labelled(#label) },
+            None => quote! { parsing::Context::new(#type_name, #field_name) }
+        };
 
         let value = match whitespace_attr {
             Some(attr) => {
                 let whitespace = attr.nested;
                 quote! {
                     {
-                        let mut __whitespace_value = value.clone();
-                        __whitespace_value.set_whitespace(parsing::charstream::WhitespaceType::#whitespace);
+                        let __whitespace = value.whitespace();
+                        value.set_whitespace(parsing::charstream::WhitespaceType::#whitespace);
 
-                        let inner =  <#ty>::parse(&mut __whitespace_value);
-                        value.goto(__whitespace_value.position())?;
+                        let inner = value.after_stop(<#ty>::parse);
+                        value.set_whitespace(__whitespace);
                         inner
                     }
                 }
             }
             None => quote! { 
-                <#ty>::parse(value)
+                value.after_stop(<#ty>::parse)
             }
         };
+        let value = quote! {
+            (#value).map_err(|error| error.with_context(#context))
+        };
         let value = match value_attr {
             Some(attr) => {
+                let expected = attr.nested.iter().map(|meta| {
+                    let name = match meta {
+                        NestedMeta::Lit(Lit::Str(lit)) => lit.value(),
+                        meta => meta.to_token_stream().to_string()
+                    };
+                    quote! { parsing::Expected::Value(::std::string::String::from(#name)) }
+                });
                 let mut values = attr.nested.iter().map(|meta| quote! { 
                     ::std::result::Result::Ok(inner) if inner == #meta => inner
                 }).collect::<Vec<_>>();
                 values.push(quote! { 
-                    ::std::result::Result::Ok(inner) => return ::std::result::Result::Err(parsing::ParseError::new("Value was not one of the expected values.", value.position()))
+                    ::std::result::Result::Ok(inner) => return ::std::result::Result::Err(parsing::ParseError::invalid_value(
+                        ::std::vec![#(#expected),*],
+                        value.source().get(inner.span().range()).map(::std::borrow::ToOwned::to_owned),
+                        inner.span()
+                    ).with_context(#context))
                 });
                 values.push(quote! { 
                     ::std::result::Result::Err(error) => return ::std::result::Result::Err(error)
@@ -198,13 +311,36 @@ fn derive_fields(fields: Vec<&Field>) -> Vec<quote::__private::TokenStream> {
                 #value?
             }
         };
-        quote! {
+        Ok(quote! {
             let #ident = #value;
+        })
+    }).collect()
+}
+
+/// Returns the generics for the Parse impl of a type, together with the lifetime of the source.
+/// The first lifetime of the type is used as the lifetime of the source, if it has none a new one is added.
+/// Every type parameter is required to implement Parse for that lifetime.
+fn parse_generics(ident: &Ident, generics: &Generics) -> (Generics, Lifetime) {
+    let mut generics = generics.clone();
+    let lifetime = match generics.lifetimes().next() {
+        Some(def) => def.lifetime.clone(),
+        None => {
+            let lifetime = Lifetime::new("'__src", ident.span());
+            generics.params.insert(0, GenericParam::Lifetime(LifetimeDef::new(lifetime.clone())));
+            lifetime
         }
-    }).collect::<Vec<_>>()
+    };
+
+    let types = generics.type_params().map(|param| param.ident.clone()).collect::<Vec<_>>();
+    let where_clause = generics.make_where_clause();
+    for ty in types {
+        where_clause.predicates.push(parse_quote! { #ty: Parse<#lifetime> });
+    }
+
+    (generics, lifetime)
 }
 
-fn get_attr(attrs: &Vec<Attribute>, value: &str) -> Option<MetaList> {
+fn get_attr(attrs: &[Attribute], value: &str) -> Option<MetaList> {
     attrs.iter().find_map(|attr| match attr.path.get_ident() {
         Some(ident) if ident == value => {
             match attr.parse_meta() {
@@ -216,6 +352,17 @@ fn get_attr(attrs: &Vec<Attribute>, value: &str) -> Option<MetaList> {
     })
 }
 
+/// Returns the label of the `#[context("...")]` attribute, or an error that points at the attribute if it is not a string.
+fn get_context(attrs: &[Attribute]) -> Result<Option<String>, TokenStream> {
+    let Some(attr) = attrs.iter().find(|attr| attr.path.is_ident("context")) else {
+        return Ok(None);
+    };
+    match attr.parse_args::<syn::LitStr>() {
+        Ok(label) => Ok(Some(label.value())),
+        Err(_) => Err(TokenStream::from(Error::new(attr.span(), "expected a label, like `#[context(\"a label\")]`").to_compile_error()))
+    }
+}
+
 fn inner_ident(ident: &Option<Ident>, index: usize) -> Ident {
     let ident = get_ident(ident, index);
     Ident::new(&format!("__inner_{}", ident), ident.span())
@@ -223,7 +370,7 @@ fn inner_ident(ident: &Option<Ident>, index: usize) -> Ident {
 
 fn get_ident(ident: &Option<Ident>, index: usize) -> quote::__private::TokenStream {
     match ident {
-        Some(ident) => <Ident as ToTokens>::to_token_stream(&ident),
+        Some(ident) => <Ident as ToTokens>::to_token_stream(ident),
         None => <Index as ToTokens>::to_token_stream(&Index::from(index))
     }
 }
\ No newline at end of file
@@ -1,4 +1,7 @@
 pub mod parsing;
 
 #[cfg(feature = "derive")]
-pub use parseal_derive::Parsable;
\ No newline at end of file
+pub use parseal_derive::Parsable;
+
+#[cfg(feature = "derive")]
+pub use parseal_derive::Printable;
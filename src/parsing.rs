@@ -1,30 +1,384 @@
 pub mod tokens;
 pub mod charstream;
+pub mod diagnostic;
+pub mod source;
+pub mod number;
+pub mod identifier;
+pub mod print;
+pub mod doc;
 
-use std::fmt;
+use std::{borrow::Cow, fmt, marker::PhantomData};
 
-use self::{charstream::{CharStream, Position, WhitespaceType, Span}, tokens::Delimiter};
+use self::charstream::{CharStream, Position, WhitespaceType, Span, Trivia};
 
-pub trait Parse: Clone {
-	fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized;
+/// Parse is implemented by everything that can be parsed from a [`CharStream`].
+/// The lifetime `'src` is the lifetime of the source, so parsed values can borrow slices of it.
+pub trait Parse<'src>: Clone {
+	fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized;
 	fn span(&self) -> Span;
 }
 
-#[derive(Clone)]
-pub struct ParseError(String, Position);
+/// The kind of failure that a [`ParseError`] describes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+	/// The input did not match any of the expected items.
+	Unexpected,
+	/// The input ended while an item was still expected.
+	UnexpectedEof,
+	/// An item was parsed, but it was not one of the allowed values.
+	InvalidValue,
+	/// Any other failure, described by its message.
+	Custom(String)
+}
+
+/// An item that a parser expected to find.
+/// The expected items of a [`ParseError`] make up its expected-set.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Expected {
+	/// A literal token, like `,` or `==`.
+	Token(String),
+	/// A named kind of item, like a string, a number or the name of a delimiter.
+	Named(String),
+	/// One specific value of an item, as required by the `#[value(...)]` attribute.
+	Value(String)
+}
+
+impl fmt::Display for Expected {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Token(token) | Self::Value(token) => write!(f, "`{}`", token),
+			Self::Named(name) => write!(f, "{}", name)
+		}
+	}
+}
+
+/// A Context is one frame of the breadcrumb trail of a [`ParseError`].
+/// It names the field of a type that was being parsed when the error happened.
+/// The `Parsable` derive adds a frame for every field, which can be relabelled with the `#[context("...")]` attribute.
+/// On a field the label replaces the whole frame, on a type or variant it replaces the type name in the frames of its fields.
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, tokens, Parse, Identifier, Number};
+/// # fn main() {
+/// 	#[cfg(feature="derive")]
+/// 	{
+/// 		# use parseal::{Parsable, parsing};
+/// 		#[derive(Parsable, Debug, Clone)]
+/// 		#[context("setting")]
+/// 		struct Setting<'a> {
+/// 			name: Identifier<'a>,
+/// 			equal: tokens::Equal,
+/// 			#[context("the value of a setting")]
+/// 			value: Number<'a>
+/// 		}
+///
+/// 		let error = Setting::parse(&mut CharStream::new("port 8080").build()).unwrap_err();
+/// 		assert_eq!(error.breadcrumbs(), "setting.equal");
+///
+/// 		let error = Setting::parse(&mut CharStream::new("port = on").build()).unwrap_err();
+/// 		assert_eq!(error.breadcrumbs(), "the value of a setting");
+/// 	}
+/// # }
+/// ```
+/// A label that is not a string is a compile error.
+/// ```compile_fail
+/// # use parseal::{Parsable, parsing::{self, Parse, Number}};
+/// #[derive(Parsable, Clone)]
+/// struct Bad<'a> {
+/// 	#[context(oops)]
+/// 	a: Number<'a>
+/// }
+/// # fn main() {}
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Context {
+	type_name: String,
+	field: String,
+	label: Option<String>
+}
+
+impl Context {
+	pub fn new(type_name: &str, field: &str) -> Self {
+		Self { type_name: type_name.to_owned(), field: field.to_owned(), label: None }
+	}
+
+	/// Creates a frame that is shown as `label`, instead of as a type and field name.
+	pub fn labelled(label: &str) -> Self {
+		Self { type_name: String::new(), field: String::new(), label: Some(label.to_owned()) }
+	}
+
+	pub fn type_name(&self) -> &str {
+		&self.type_name
+	}
+
+	pub fn field(&self) -> &str {
+		&self.field
+	}
+
+	pub fn label(&self) -> Option<&str> {
+		self.label.as_deref()
+	}
+}
+
+impl fmt::Display for Context {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match &self.label {
+			Some(label) => write!(f, "{}", label),
+			None => write!(f, "{}.{}", self.type_name, self.field)
+		}
+	}
+}
+
+/// A ParseError describes why a value could not be parsed.
+/// It stores what kind of error it is, the set of items that were expected,
+/// what was found instead and the [`Span`] of the input that caused the error.
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, tokens, ErrorKind, Expected, Parse};
+/// # fn main() {
+/// 	let mut buffer = CharStream::new("; ").build();
+/// 	let error = tokens::Comma::parse(&mut buffer).unwrap_err();
+///
+/// 	assert_eq!(error.kind(), &ErrorKind::Unexpected);
+/// 	assert_eq!(error.expected(), &[Expected::Token(",".to_owned())]);
+/// 	assert_eq!(error.found(), Some(";"));
+///
+/// 	let mut buffer = CharStream::new("").build();
+/// 	let error = tokens::Comma::parse(&mut buffer).unwrap_err();
+///
+/// 	assert_eq!(error.kind(), &ErrorKind::UnexpectedEof);
+/// # }
+/// ```
+/// A field with the `#[value(...)]` attribute that parsed to another value gives an [`ErrorKind::InvalidValue`] error.
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, ErrorKind, Identifier, Parse};
+/// # fn main() {
+/// 	#[cfg(feature="derive")]
+/// 	{
+/// 		# use parseal::{Parsable, parsing};
+/// 		#[derive(Parsable, Debug, Clone)]
+/// 		struct Bool<'a>(#[value("true", "false")] Identifier<'a>);
+///
+/// 		let error = Bool::parse(&mut CharStream::new(" maybe").build()).unwrap_err();
+/// 		assert_eq!(error.kind(), &ErrorKind::InvalidValue);
+/// 		assert_eq!(error.found(), Some("maybe"));
+/// 		assert_eq!(error.message(), "expected one of `true`, `false`, found `maybe`");
+/// 	}
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+#[derive(Clone, PartialEq)]
+pub struct ParseError(Box<ErrorData>);
+
+/// The data of a [`ParseError`], boxed so that results of parsers stay small.
+#[derive(Clone, PartialEq)]
+struct ErrorData {
+	kind: ErrorKind,
+	expected: Vec<Expected>,
+	found: Option<String>,
+	span: Span,
+	context: Vec<Context>
+}
 
 impl ParseError {
+	/// Creates an error of kind [`ErrorKind::Custom`], with `cause` as its message.
 	pub fn new(cause: &str, position: Position) -> Self {
-		Self(cause.to_string(), position)
+		Self::custom(cause, Span::new(position, position))
+	}
+
+	pub fn custom(cause: &str, span: Span) -> Self {
+		Self(Box::new(ErrorData { kind: ErrorKind::Custom(cause.to_string()), expected: Vec::new(), found: None, span, context: Vec::new() }))
+	}
+
+	pub fn unexpected(expected: Expected, found: &str, span: Span) -> Self {
+		Self(Box::new(ErrorData { kind: ErrorKind::Unexpected, expected: vec![expected], found: Some(found.to_string()), span, context: Vec::new() }))
+	}
+
+	pub fn eof(expected: Expected, position: Position) -> Self {
+		Self(Box::new(ErrorData { kind: ErrorKind::UnexpectedEof, expected: vec![expected], found: None, span: Span::new(position, position), context: Vec::new() }))
+	}
+
+	pub fn invalid_value(expected: Vec<Expected>, found: Option<String>, span: Span) -> Self {
+		Self(Box::new(ErrorData { kind: ErrorKind::InvalidValue, expected, found, span, context: Vec::new() }))
+	}
+
+	/// Replaces the expected-set of this error.
+	/// This is used by parsers that are built on top of other parsers,
+	/// to report what they expected instead of what their parts expected.
+	pub fn with_expected(mut self, expected: Vec<Expected>) -> Self {
+		self.0.expected = expected;
+		self
+	}
+
+	/// Merges two errors of alternatives that were parsed from the same position.
+	/// The error that got furthest into the input is kept.
+	/// If both errors failed at the same position, their expected-sets are combined.
+	/// ```
+	/// # use parseal::parsing::{charstream::CharStream, tokens, Parse, Number, StringValue};
+	/// # fn main() {
+	/// 	let buffer = CharStream::new("[ true ]").build();
+	///
+	/// 	let string = StringValue::parse(&mut buffer.clone()).unwrap_err();
+	/// 	let number = Number::parse(&mut buffer.clone()).unwrap_err();
+	/// 	let error = string.merge(number);
+	/// 	assert_eq!(error.message(), "expected one of string, number, found `[`");
+	///
+	/// 	let list = <(tokens::LeftBracket, Number)>::parse(&mut buffer.clone()).unwrap_err();
+	/// 	let error = error.merge(list);
+	/// 	assert_eq!(error.message(), "expected number, found `t`");
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn merge(mut self, other: ParseError) -> Self {
+		match self.0.span.start.partial_cmp(&other.0.span.start) {
+			Some(std::cmp::Ordering::Less) => other,
+			Some(std::cmp::Ordering::Equal) => {
+				if let (ErrorKind::Custom(_), kind) = (&self.0.kind, &other.0.kind) {
+					if !matches!(kind, ErrorKind::Custom(_)) {
+						self.0.kind = other.0.kind;
+						self.0.found = other.0.found;
+					}
+				}
+
+				for expected in other.0.expected {
+					if !self.0.expected.contains(&expected) {
+						self.0.expected.push(expected);
+					}
+				}
+
+				// only the outer frames that both alternatives share still describe the merged error.
+				let shared = self.0.context.iter().rev()
+					.zip(other.0.context.iter().rev())
+					.take_while(|(left, right)| left == right)
+					.count();
+				self.0.context.drain(..self.0.context.len() - shared);
+				self
+			}
+			_ => self
+		}
+	}
+
+	/// Adds a frame around the current context of this error.
+	/// Frames are added from the inside out, as the error is returned through the parsers of enclosing types.
+	pub fn with_context(mut self, context: Context) -> Self {
+		self.0.context.push(context);
+		self
+	}
+
+	pub fn kind(&self) -> &ErrorKind {
+		&self.0.kind
+	}
+
+	pub fn expected(&self) -> &[Expected] {
+		&self.0.expected
+	}
+
+	pub fn found(&self) -> Option<&str> {
+		self.0.found.as_deref()
+	}
+
+	pub fn span(&self) -> &Span {
+		&self.0.span
+	}
+
+	/// Returns the context frames of this error, from the innermost to the outermost frame.
+	pub fn context(&self) -> &[Context] {
+		&self.0.context
+	}
+
+	/// Returns the context frames as a breadcrumb trail, from the outermost to the innermost frame.
+	/// ```
+	/// # use parseal::parsing::{charstream::CharStream, Context, Parse, Number};
+	/// # fn main() {
+	/// 	let mut buffer = CharStream::new("x").build();
+	/// 	let error = Number::parse(&mut buffer).unwrap_err()
+	/// 		.with_context(Context::new("NamedValue", "value"))
+	/// 		.with_context(Context::new("JSONObject", "map"));
+	///
+	/// 	assert_eq!(error.breadcrumbs(), "JSONObject.map > NamedValue.value");
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn breadcrumbs(&self) -> String {
+		self.0.context.iter().rev().map(|context| context.to_string()).collect::<Vec<_>>().join(" > ")
+	}
+
+	/// Shows this error with the file, line and column it happened at in the source of `value`,
+	/// instead of the byte offset that the [`fmt::Display`] implementation shows.
+	/// ```
+	/// # use parseal::parsing::{charstream::CharStream, tokens, Group, List, Number, Parse};
+	/// # fn main() {
+	/// 	let mut buffer = CharStream::new("[1,\n 2 3]").file("list.conf").build();
+	/// 	let error = Group::<tokens::Bracket, List<Number, tokens::Comma>>::parse(&mut buffer).unwrap_err();
+	///
+	/// 	assert_eq!(error.to_string(), "expected one of `,`, `]`, found `3` at byte 7");
+	/// 	assert_eq!(error.display_in(&buffer), "expected one of `,`, `]`, found `3` at list.conf:2:4");
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn display_in(&self, value: &CharStream) -> String {
+		let position = self.0.span.start;
+		if position.source_id != value.source_id() || position.offset > value.source().len() {
+			return self.to_string();
+		}
+
+		let line_col = value.line_col(position);
+		let mut text = String::new();
+		let _ = match value.file() {
+			Some(file) => self.write(&mut text, &format_args!("{}:{}", file, line_col)),
+			None => self.write(&mut text, &line_col)
+		};
+		text
+	}
+
+	/// Shows this error with the file, line and column it happened at, looked up in `sources`.
+	pub fn display_with(&self, sources: &source::SourceMap) -> String {
+		let mut text = String::new();
+		let _ = match sources.resolve(self.0.span.start) {
+			Some(location) => self.write(&mut text, &location),
+			None => self.write(&mut text, &self.0.span.start)
+		};
+		text
+	}
+
+	fn write(&self, f: &mut impl fmt::Write, location: &dyn fmt::Display) -> fmt::Result {
+		write!(f, "{} at {}", self.message(), location)?;
+		if !self.0.context.is_empty() {
+			write!(f, " while parsing {}", self.breadcrumbs())?;
+		}
+		Ok(())
+	}
+
+	/// Returns the message of this error, without its position.
+	pub fn message(&self) -> String {
+		let expected = match self.0.expected.as_slice() {
+			[] => String::from("expected nothing"),
+			[expected] => format!("expected {}", expected),
+			expected => format!("expected one of {}", expected.iter().map(|item| item.to_string()).collect::<Vec<_>>().join(", "))
+		};
+
+		match (&self.0.kind, &self.0.found) {
+			(ErrorKind::Custom(cause), _) => cause.clone(),
+			(ErrorKind::UnexpectedEof, _) => format!("{}, found end of input", expected),
+			(_, Some(found)) => format!("{}, found `{}`", expected, found),
+			(_, None) => expected
+		}
+	}
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.write(f, &self.0.span.start)
 	}
 }
 
 impl fmt::Debug for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "{}:{}:Error: '{}'", self.1.row, self.1.column, self.0)
+		write!(f, "{}:Error: '{}'", self.0.span.start, self.message())
     }
 }
 
+impl std::error::Error for ParseError {}
+
 /// A Group represents a delimited item.
 /// Group has two Generic types:
 /// - `D` is the delimiter tokens around the item, it has to a type that implements [`tokens::Delimiter`].
@@ -32,40 +386,43 @@ impl fmt::Debug for ParseError {
 /// ```
 /// # use parseal::parsing::{charstream::CharStream, tokens, Group, StringValue, Number, List, Parse};
 /// # fn main() {
-/// 	let buffer = "(\"Hello, World\")".to_owned();
+/// 	let buffer = "(\"Hello, World\")";
 /// 	let mut buffer = CharStream::new(buffer).build();
 /// 
 /// 	let value = Group::<tokens::Paren, StringValue>::parse(&mut buffer);
 /// 	assert!(value.is_ok());
 /// 
-/// 	let buffer = "[0, 1, 2]".to_owned();
+/// 	let buffer = "[0, 1, 2]";
 /// 	let mut buffer = CharStream::new(buffer).build();
 /// 
 /// 	let value = Group::<tokens::Bracket, List<Number, tokens::Comma>>::parse(&mut buffer);
 /// 	assert!(value.is_ok());
 /// # }
 /// ```
+#[allow(clippy::tabs_in_doc_comments)]
 #[derive(Clone)]
-pub struct Group<D, I> where D: tokens::Delimiter, I: Parse {
+pub struct Group<D, I> where D: tokens::Delimiter {
 	delimiter: D,
-	item: I
+	item: Result<I, ErrorNode>
 }
 
-impl<D, I> Parse for Group<D, I> where
+impl<'src, D, I> Parse<'src> for Group<D, I> where
 	D: tokens::Delimiter,
-	I: Parse
+	I: Parse<'src>
 {
-    fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized {
-		let start = D::Start::parse(value)?;
-		let item = I::parse(value)?;
-		let end = match D::End::parse(value) {
-			Ok(value) => value,
-			Err(error) => return Err(error)
-		};
+    fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized {
+		value.attempt(|value| {
+			let start = D::Start::parse(value)?;
+
+			value.push_closer(|value| D::End::parse(value).is_ok());
+			let inner = Self::parse_inner(value);
+			value.pop_closer();
+			let (item, end) = inner?;
 
-		let delimiter = D::new(start, end);
+			let delimiter = D::new(start, end);
 
-		Ok(Self { delimiter, item })
+			Ok(Self { delimiter, item })
+		})
     }
 
 	fn span(&self) -> Span {
@@ -73,12 +430,65 @@ impl<D, I> Parse for Group<D, I> where
 	}
 }
 
+impl<'src, D, I> Group<D, I> where
+	D: tokens::Delimiter,
+	I: Parse<'src>
+{
+	fn parse_inner(value: &mut CharStream<'src>) -> Result<(Result<I, ErrorNode>, D::End), ParseError> {
+		let item = match value.attempt(I::parse) {
+			Ok(item) => Ok(item),
+			Err(error) if value.is_recovering() => Err(ErrorNode::recover(value, error, |_| false)),
+			Err(error) => return Err(error)
+		};
+
+		let end = match value.after_stop(D::End::parse) {
+			Ok(end) => end,
+			Err(error) if value.is_recovering() => {
+				synchronize(value, |_| false);
+				value.diagnostics().error(error);
+				// a closer that is still not there, like at the end of the source, is inserted so the group is kept.
+				D::End::parse(value).unwrap_or_else(|_| <D::End as tokens::Token>::missing(value.position()))
+			}
+			Err(error) => return Err(error)
+		};
+
+		Ok((item, end))
+	}
+}
+
+impl<D, I> Group<D, I> where D: tokens::Delimiter {
+	/// Returns the item between the delimiters, or the [`ErrorNode`] that took its place in recovery mode.
+	/// ```
+	/// # use parseal::parsing::{charstream::CharStream, tokens::{self, Delimiter}, Group, Identifier, Parse};
+	/// # fn main() {
+	/// 	let mut buffer = CharStream::new("( name )").build();
+	/// 	let group = Group::<tokens::Paren, Identifier>::parse(&mut buffer).unwrap();
+	///
+	/// 	assert_eq!(group.inner().unwrap().as_str(), "name");
+	/// 	assert_eq!(group.delimiters().end().span().start.offset, 7);
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn inner(&self) -> Result<&I, &ErrorNode> {
+		self.item.as_ref()
+	}
+
+	pub fn into_inner(self) -> Result<I, ErrorNode> {
+		self.item
+	}
+
+	/// Returns the delimiter tokens around the item.
+	pub fn delimiters(&self) -> &D {
+		&self.delimiter
+	}
+}
+
 impl<D, I> fmt::Debug for Group<D, I> where
 	D: tokens::Delimiter,
-	I: Parse + fmt::Debug
+	I: fmt::Debug
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "Group({:#?}, delim: {}, from {})", self.item, D::name(), self.span())
+		write!(f, "Group({:#?}, delim: {}, from {})", Recovered(&self.item), D::name(), self.delimiter.span())
     }
 }
 
@@ -88,146 +498,933 @@ impl<D, I> fmt::Debug for Group<D, I> where
 /// - `I` is the type of item, it has to implement [`Parse`].
 /// - `S` is the token that separates the items. it has to implement [`tokens::Token`].
 /// ```
-/// # use parseal::parsing::{charstream::CharStream, tokens, Group, StringValue, Number, List, Parse};
+/// # use parseal::parsing::{charstream::CharStream, tokens, Group, StringValue, Number, List, Parse};
+/// # fn main() {
+/// 	let buffer = "0, 1, 5";
+/// 	let mut buffer = CharStream::new(buffer).build();
+/// 
+/// 	let value = List::<Number, tokens::Comma>::parse(&mut buffer);
+/// 	assert!(value.is_ok());
+/// 
+/// 	let buffer = "";
+/// 	let mut buffer = CharStream::new(buffer).build();
+/// 
+/// 	let value = List::<StringValue, tokens::Pipe>::parse(&mut buffer);
+/// 	assert!(value.is_ok()); 
+/// 	// A List can also be empty.
+/// 
+/// 	let buffer = "1012";
+/// 	let mut buffer = CharStream::new(buffer).build();
+/// 
+/// 	let value = List::<StringValue, tokens::Pipe>::parse(&mut buffer);
+/// 	assert!(value.is_ok()); 
+/// 	// the parse function is not guaranteed to consume the entire buffer.
+/// 	// in this case it will not consume anything from the buffer, yet return an Ok variant, as the List is allowed to be empty.
+/// # }
+/// ```
+///
+/// When the [`CharStream`] is in recovery mode, an item that fails to parse is skipped up to the next separator,
+/// or up to the closing delimiter of the [`Group`] around the list. An [`ErrorNode`] takes its place and the error is recorded in the stream.
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, print::Printer, tokens, Group, Number, List, Parse};
+/// # fn main() {
+/// 	let mut buffer = CharStream::new("[0, x, 2, 3 4]").recover(true).build();
+///
+/// 	let value = Group::<tokens::Bracket, List<Number, tokens::Comma>>::parse(&mut buffer);
+/// 	assert!(value.is_ok());
+///
+/// 	let errors = buffer.diagnostics().errors();
+/// 	assert_eq!(errors.len(), 2);
+/// 	assert_eq!(errors[0].message(), "expected number, found `x`");
+/// 	assert_eq!(errors[1].message(), "expected one of `,`, `]`, found `4`");
+///
+/// 	// a closing delimiter that is missing is inserted, without any text, where the group ends.
+/// 	let mut buffer = CharStream::new("[[1, 2], [3").recover(true).build();
+/// 	let value = Group::<tokens::Bracket, List<Group<tokens::Bracket, List<Number, tokens::Comma>>, tokens::Comma>>::parse(&mut buffer).unwrap();
+/// 	assert_eq!(value.inner().unwrap().len(), 2);
+/// 	assert_eq!(value.span().end.offset, 11);
+/// 	assert_eq!(Printer::new().print(&value), "[[1, 2], [3]]");
+///
+/// 	let errors = buffer.diagnostics().errors();
+/// 	assert_eq!(errors.len(), 2);
+/// 	assert_eq!(errors[0].message(), "expected one of `,`, `]`, found end of input");
+/// 	assert_eq!(errors[0].span().start.offset, 11);
+/// # }
+/// ```
+///
+/// A list ends at the first item or separator that fails to parse. That error is kept in the stream with [`CharStream::stop`],
+/// and merged into the error of what comes after the list, so a nested error is not hidden behind the closing delimiter.
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, tokens, Parse, Group, List, StringValue, Number, Expected};
+/// # fn main() {
+/// 	#[cfg(feature="derive")]
+/// 	{
+/// 		# use parseal::{Parsable, parsing};
+/// 		#[derive(Parsable, Debug, Clone)]
+/// 		enum Value<'a> {
+/// 			Number(Number<'a>),
+/// 			String(StringValue<'a>),
+/// 			Array(Group<tokens::Bracket, List<Value<'a>, tokens::Comma>>),
+/// 			Object(Group<tokens::Brace, List<(StringValue<'a>, tokens::Colon, Value<'a>), tokens::Comma>>)
+/// 		}
+///
+/// 		let error = Value::parse(&mut CharStream::new(r#"{"a": {"b": [1, 2,, 3]}}"#).build()).unwrap_err();
+/// 		assert_eq!(error.span().start.offset, 18);
+/// 		assert_eq!(error.message(), "expected one of number, string, `[`, `{`, found `,`");
+///
+/// 		let error = Value::parse(&mut CharStream::new(r#"{"a": 1 "b": 2}"#).build()).unwrap_err();
+/// 		assert_eq!(error.span().start.offset, 8);
+/// 		assert_eq!(error.expected(), &[Expected::Token(",".to_owned()), Expected::Token("}".to_owned())]);
+/// 	}
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+#[derive(Clone)]
+pub struct List<I, S> where S: tokens::Token {
+	items: Vec<(Result<I, ErrorNode>, Option<S>)>,
+	span: Span
+}
+
+impl<'src, I, S> Parse<'src> for List<I, S> where
+	I: Parse<'src>,
+	S: tokens::Token
+{
+	fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized {
+        value.attempt(|value| {
+			let mut items = Vec::new();
+			let start = value.position();
+
+			loop {
+				let item = match value.attempt(I::parse) {
+					Ok(item) => Ok(item),
+					Err(error) => {
+						let recoverable = value.is_recovering()
+							&& (!items.is_empty() || (value.in_delimiter() && !value.at_closer() && !value.at_end()));
+
+						if recoverable {
+							Err(ErrorNode::recover(value, error, |value| value.lookahead(S::parse)))
+						} else if !items.is_empty() {
+							return Err(error);
+						} else {
+							value.stop(error);
+							break
+						}
+					}
+				};
+
+				let separator = match S::parse(value) {
+					Ok(value) => Some(value),
+					Err(error) => {
+						value.stop(error);
+						items.push((item, None));
+						break;
+					}
+				};
+
+				items.push((item, separator));
+			}
+
+			let end = value.position();
+
+			Ok(Self { items, span: Span::new(start, end) })
+		})
+    }
+
+	fn span(&self) -> Span {
+		self.span
+	}
+}
+
+impl<I, S> List<I, S> where S: tokens::Token {
+	/// Returns an iterator over the items of the list.
+	/// Items that were replaced by an [`ErrorNode`] in recovery mode are skipped, they can be found with [`List::errors`].
+	/// ```
+	/// # use parseal::parsing::{charstream::CharStream, tokens, Number, List, Parse};
+	/// # fn main() {
+	/// 	let mut buffer = CharStream::new("1, 2, 3").build();
+	/// 	let list = List::<Number, tokens::Comma>::parse(&mut buffer).unwrap();
+	///
+	/// 	assert_eq!(list.len(), 3);
+	/// 	assert_eq!(list.separators().count(), 2);
+	///
+	/// 	let sum: i64 = list.iter().map(|number| number.as_i64().unwrap()).sum();
+	/// 	assert_eq!(sum, 6);
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn iter(&self) -> <&Self as IntoIterator>::IntoIter {
+		self.into_iter()
+	}
+
+	/// Returns the number of items in the list, not counting the ones that were replaced by an [`ErrorNode`].
+	pub fn len(&self) -> usize {
+		self.iter().count()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Returns an iterator over the separators between the items.
+	pub fn separators(&self) -> impl Iterator<Item = &S> {
+		self.items.iter().filter_map(|(_, separator)| separator.as_ref())
+	}
+
+	/// Returns an iterator over the [`ErrorNode`]s that took the place of items in recovery mode.
+	pub fn errors(&self) -> impl Iterator<Item = &ErrorNode> {
+		self.items.iter().filter_map(|(item, _)| item.as_ref().err())
+	}
+
+	/// Returns an iterator over every item or [`ErrorNode`], together with the separator after it.
+	pub fn entries(&self) -> impl Iterator<Item = (Result<&I, &ErrorNode>, Option<&S>)> {
+		self.items.iter().map(|(item, separator)| (item.as_ref(), separator.as_ref()))
+	}
+
+	pub fn into_vec(self) -> Vec<I> {
+		self.into_iter().collect()
+	}
+}
+
+type Entry<I, S> = (Result<I, ErrorNode>, Option<S>);
+
+impl<I, S> IntoIterator for List<I, S> where S: tokens::Token {
+	type Item = I;
+	type IntoIter = std::iter::FilterMap<std::vec::IntoIter<Entry<I, S>>, fn(Entry<I, S>) -> Option<I>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.into_iter().filter_map(|(item, _)| item.ok())
+	}
+}
+
+impl<'a, I, S> IntoIterator for &'a List<I, S> where S: tokens::Token {
+	type Item = &'a I;
+	type IntoIter = std::iter::FilterMap<std::slice::Iter<'a, Entry<I, S>>, fn(&'a Entry<I, S>) -> Option<&'a I>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.iter().filter_map(|(item, _)| item.as_ref().ok())
+	}
+}
+
+impl<I, S> fmt::Debug for List<I, S> where 
+	I: fmt::Debug,
+	S: tokens::Token + fmt::Debug
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let items = self.items.iter().map(|(item, separator)| (Recovered(item), separator)).collect::<Vec<_>>();
+		write!(f, "List({:#?}, from {})", items, self.span)
+    }
+}
+
+/// An ErrorNode takes the place of an item that failed to parse while the [`CharStream`] was in recovery mode.
+/// It stores the error and the span of the input that was skipped.
+#[derive(Clone)]
+pub struct ErrorNode {
+	error: ParseError,
+	span: Span,
+	trivia: Trivia
+}
+
+impl ErrorNode {
+	/// Skips input up to the next point where parsing can continue, records `error` in the stream,
+	/// and creates a node for the skipped input.
+	fn recover<'src>(value: &mut CharStream<'src>, error: ParseError, at_sync: impl Fn(&mut CharStream<'src>) -> bool) -> Self {
+		let leading = value.leading_trivia();
+		let start = value.position();
+
+		synchronize(value, at_sync);
+		value.diagnostics().error(error.clone());
+
+		let span = Span::new(start, value.position());
+		Self { error, span, trivia: Trivia::new(leading, value.trailing_trivia()) }
+	}
+
+	pub fn error(&self) -> &ParseError {
+		&self.error
+	}
+
+	pub fn span(&self) -> Span {
+		self.span
+	}
+
+	pub fn trivia(&self) -> Trivia {
+		self.trivia
+	}
+}
+
+impl fmt::Debug for ErrorNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "ErrorNode({}, from {})", self.error.message(), self.span)
+    }
+}
+
+/// Skips input until `at_sync` succeeds, the closing side of the enclosing delimiter is found, or the input ends.
+fn synchronize<'src>(value: &mut CharStream<'src>, at_sync: impl Fn(&mut CharStream<'src>) -> bool) {
+	while !at_sync(value) && !value.at_closer() && value.next().is_some() {}
+}
+
+/// Formats an item that might have been replaced by an [`ErrorNode`] as the item itself.
+struct Recovered<'a, I>(&'a Result<I, ErrorNode>);
+
+impl<I> fmt::Debug for Recovered<'_, I> where I: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.0 {
+			Ok(item) => item.fmt(f),
+			Err(node) => node.fmt(f)
+		}
+    }
+}
+
+/// StringValue represents a string between double quotes.
+/// this is necessary because it needs to store some additional information for the AST, like the info necessary for [`Parse::span`].
+/// ```
+/// # use parseal::parsing::{StringValue, Parse, charstream::CharStream};
+/// # fn main() {
+/// 	let mut buffer = CharStream::new("\"Hello, world!\"").build();
+/// 	let value = StringValue::parse(&mut buffer);
+/// 
+/// 	assert!(value.is_ok());
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+pub type StringValue<'src> = QuotedString<'src, tokens::Quote>;
+
+/// A QuotedString is a string between the two sides of the delimiter `Q`, like [`tokens::Quote`] or [`tokens::SingleQuote`].
+/// Escape sequences are decoded: `\"`, `\'`, `\\`, `\/`, `\n`, `\t`, `\r`, `\b`, `\f`, `\0` and `\uXXXX`, where a surrogate pair of `\u` escapes makes one character.
+/// Both the text as it was written and the decoded value are kept. The decoded value only allocates when the string contains escapes.
+/// ```
+/// # use parseal::parsing::{QuotedString, StringValue, Parse, charstream::CharStream, tokens};
+/// # fn main() {
+/// 	let mut buffer = CharStream::new(r#""a \"quoted\" word\n""#).build();
+/// 	let value = StringValue::parse(&mut buffer).unwrap();
+/// 	assert_eq!(value.raw(), r#"a \"quoted\" word\n"#);
+/// 	assert_eq!(value.value(), "a \"quoted\" word\n");
+///
+/// 	let mut buffer = CharStream::new(r"'😀 it\'s'").build();
+/// 	let value = QuotedString::<tokens::SingleQuote>::parse(&mut buffer).unwrap();
+/// 	assert_eq!(value.value(), "😀 it's");
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+#[derive(Clone)]
+pub struct QuotedString<'src, Q> where Q: tokens::Delimiter {
+	delim: Q,
+	raw: &'src str,
+	value: Cow<'src, str>,
+	trivia: Trivia
+}
+
+impl<'src, Q> QuotedString<'src, Q> where Q: tokens::Delimiter {
+	/// Returns the text between the delimiters as it was written, with the escape sequences still in it.
+	pub fn raw(&self) -> &'src str {
+		self.raw
+	}
+
+	/// Returns the text between the delimiters, with the escape sequences decoded.
+	pub fn value(&self) -> &str {
+		&self.value
+	}
+
+	/// Returns the decoded text, which only owns its text if the string contained escape sequences.
+	pub fn into_value(self) -> Cow<'src, str> {
+		self.value
+	}
+
+	/// Returns the trivia around the string, which is only recorded in a lossless stream.
+	pub fn trivia(&self) -> Trivia {
+		self.trivia
+	}
+
+	fn parse_content(value: &mut CharStream<'src>) -> Result<(Q, &'src str, Option<String>), ParseError> {
+		let left = Q::Start::parse(value)
+			.map_err(|error| error.with_expected(vec![Expected::Named("string".to_owned())]))?;
+		let start = value.offset();
+		let mut decoded: Option<String> = None;
+
+		loop {
+			let offset = value.offset();
+			match Q::End::parse(value) {
+				Ok(end) => return Ok((Q::new(left, end), &value.source()[start..offset], decoded)),
+				Err(error) if value.at_end() => return Err(error),
+				Err(_) => {}
+			}
+
+			let escape_start = value.position();
+			match value.next() {
+				Some('\\') => {
+					let chr = escape(value, escape_start)?;
+					decoded.get_or_insert_with(|| value.source()[start..offset].to_owned()).push(chr);
+				}
+				Some(chr) => if let Some(decoded) = &mut decoded {
+					decoded.push(chr);
+				}
+				None => unreachable!("the end of the input is checked before reading a character")
+			}
+		}
+	}
+}
+
+impl<'src, Q> Parse<'src> for QuotedString<'src, Q> where Q: tokens::Delimiter {
+	fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized {
+		value.attempt(|value| {
+			let leading = value.leading_trivia();
+			let whitespace = value.whitespace();
+			value.set_whitespace(WhitespaceType::KeepAll);
+			let content = Self::parse_content(value);
+			value.set_whitespace(whitespace);
+			let (delim, raw, decoded) = content?;
+
+			let trivia = Trivia::new(leading, value.trailing_trivia());
+			let value = match decoded {
+				Some(decoded) => Cow::Owned(decoded),
+				None => Cow::Borrowed(raw)
+			};
+
+			Ok(Self { delim, raw, value, trivia })
+		})
+    }
+
+	fn span(&self) -> Span {
+		self.delim.span()
+	}
+}
+
+impl<Q> fmt::Debug for QuotedString<'_, Q> where Q: tokens::Delimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "StringValue({}, from {})", self.value, self.span())
+    }
+}
+
+/// A RawString is a Rust-style raw string, like `r"C:\path"` or `r#"a "quoted" word"#`.
+/// Nothing in it is escaped, it ends at the first `"` that is followed by as many `#`s as it started with.
+/// ```
+/// # use parseal::parsing::{RawString, Parse, charstream::CharStream};
+/// # fn main() {
+/// 	let mut buffer = CharStream::new(r###"r#"a "quoted" \word"#"###).build();
+/// 	let value = RawString::parse(&mut buffer).unwrap();
+///
+/// 	assert_eq!(value.value(), r#"a "quoted" \word"#);
+/// 	assert_eq!(value.hashes(), 1);
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+#[derive(Clone)]
+pub struct RawString<'src> {
+	value: &'src str,
+	hashes: usize,
+	span: Span,
+	trivia: Trivia
+}
+
+impl<'src> RawString<'src> {
+	pub fn value(&self) -> &'src str {
+		self.value
+	}
+
+	/// Returns the number of `#`s around the string.
+	pub fn hashes(&self) -> usize {
+		self.hashes
+	}
+
+	/// Returns the trivia around the string, which is only recorded in a lossless stream.
+	pub fn trivia(&self) -> Trivia {
+		self.trivia
+	}
+
+	fn parse_content(value: &mut CharStream<'src>) -> Result<(&'src str, usize), ParseError> {
+		let mut hashes = 0;
+		while value.peek() == Some('#') {
+			value.next();
+			hashes += 1;
+		}
+
+		let quote = value.position();
+		match value.next() {
+			Some('"') => {}
+			Some(chr) => return Err(ParseError::unexpected(Expected::Token("\"".to_owned()), &chr.to_string(), Span::new(quote, value.position()))),
+			None => return Err(ParseError::eof(Expected::Token("\"".to_owned()), value.position()))
+		}
+
+		let start = value.offset();
+		loop {
+			let end = value.offset();
+			match value.next() {
+				Some('"') => {
+					let mut closing = 0;
+					while closing < hashes && value.peek() == Some('#') {
+						value.next();
+						closing += 1;
+					}
+
+					if closing == hashes {
+						return Ok((&value.source()[start..end], hashes));
+					}
+				}
+				Some(_) => {}
+				None => return Err(ParseError::eof(Expected::Token(format!("\"{}", "#".repeat(hashes))), value.position()))
+			}
+		}
+	}
+}
+
+impl<'src> Parse<'src> for RawString<'src> {
+	fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized {
+		value.attempt(|value| {
+			let leading = value.leading_trivia();
+			let start = value.position();
+			match value.next() {
+				Some('r') => {}
+				Some(chr) => return Err(ParseError::unexpected(Expected::Named("raw string".to_owned()), &chr.to_string(), Span::new(start, value.position()))),
+				None => return Err(ParseError::eof(Expected::Named("raw string".to_owned()), value.position()))
+			}
+
+			let whitespace = value.whitespace();
+			value.set_whitespace(WhitespaceType::KeepAll);
+			let content = Self::parse_content(value);
+			value.set_whitespace(whitespace);
+			let (inner_value, hashes) = content?;
+
+			let span = Span::new(start, value.position());
+			Ok(Self { value: inner_value, hashes, span, trivia: Trivia::new(leading, value.trailing_trivia()) })
+		})
+	}
+
+	fn span(&self) -> Span {
+		self.span
+	}
+}
+
+impl fmt::Debug for RawString<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "RawString({}, from {})", self.value, self.span)
+    }
+}
+
+/// MultilineString is a string between triple double quotes, like the multi-line strings of TOML and Python.
+/// ```
+/// # use parseal::parsing::{MultilineString, Number, Parse, charstream::CharStream, tokens};
 /// # fn main() {
-/// 	let buffer = "0, 1, 5".to_owned();
-/// 	let mut buffer = CharStream::new(buffer).build();
-/// 
-/// 	let value = List::<Number, tokens::Comma>::parse(&mut buffer);
-/// 	assert!(value.is_ok());
-/// 
-/// 	let buffer = "".to_owned();
-/// 	let mut buffer = CharStream::new(buffer).build();
-/// 
-/// 	let value = List::<StringValue, tokens::Pipe>::parse(&mut buffer);
-/// 	assert!(value.is_ok()); 
-/// 	// A List can also be empty.
-/// 
-/// 	let buffer = "1012".to_owned();
-/// 	let mut buffer = CharStream::new(buffer).build();
-/// 
-/// 	let value = List::<StringValue, tokens::Pipe>::parse(&mut buffer);
-/// 	assert!(value.is_ok()); 
-/// 	// the parse function is not guaranteed to consume the entire buffer.
-/// 	// in this case it will not consume anything from the buffer, yet return an Ok variant, as the List is allowed to be empty.
+/// 	let mut buffer = CharStream::new("\"\"\"\nfirst line\nsecond \"line\"\"\"\"").build();
+/// 	let value = MultilineString::parse(&mut buffer).unwrap();
+///
+/// 	assert_eq!(value.value(), "first line\nsecond \"line\"");
+///
+/// 	let mut buffer = CharStream::new("\"\"\"one \\  \n   two\"\"\"").build();
+/// 	assert_eq!(MultilineString::parse(&mut buffer).unwrap().value(), "one two");
+///
+/// 	let mut buffer = CharStream::new("\"\"\"one\\ two\"\"\"").build();
+/// 	assert_eq!(MultilineString::parse(&mut buffer).unwrap_err().message(), "expected escape sequence, found `\\ `");
+///
+/// 	// the error that ended the repetition before the string is not merged into the error of the string itself.
+/// 	let mut buffer = CharStream::new("\"1 \"x").build();
+/// 	assert_eq!(Vec::<(tokens::LeftQuote, Number)>::parse(&mut buffer).unwrap().len(), 1);
+/// 	assert_eq!(MultilineString::parse(&mut buffer).unwrap_err().message(), "expected `\"`, found `x`");
 /// # }
 /// ```
+#[allow(clippy::tabs_in_doc_comments)]
+pub type MultilineString<'src> = TripleQuotedString<'src, tokens::Quote>;
+
+/// A TripleQuotedString is a string between three of the delimiter `Q` on each side, which can span multiple lines.
+/// Escape sequences are decoded like in a [`QuotedString`], and a `\` at the end of a line removes the line break and the whitespace after it.
+/// The `\` can be followed by spaces and tabs before the line break, but anywhere else a `\` followed by whitespace is an invalid escape.
+/// Like in TOML, a line break right after the opening delimiter is not part of the value.
 #[derive(Clone)]
-pub struct List<I, S> where I: Parse, S: tokens::Token {
-	items: Vec<(I, Option<S>)>,
-	span: Span
+pub struct TripleQuotedString<'src, Q> where Q: tokens::Delimiter {
+	raw: &'src str,
+	value: Cow<'src, str>,
+	span: Span,
+	trivia: Trivia,
+	delimiter: PhantomData<Q>
 }
 
-impl<I, S> Parse for List<I, S> where
-	I: Parse,
-	S: tokens::Token
-{
-	fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized {
-        let mut items = Vec::new();
-		let start = value.position();
+impl<'src, Q> TripleQuotedString<'src, Q> where Q: tokens::Delimiter {
+	/// Returns the text between the delimiters as it was written.
+	pub fn raw(&self) -> &'src str {
+		self.raw
+	}
+
+	/// Returns the text between the delimiters, with the escape sequences decoded and the leading line break removed.
+	pub fn value(&self) -> &str {
+		&self.value
+	}
+
+	/// Returns the trivia around the string, which is only recorded in a lossless stream.
+	pub fn trivia(&self) -> Trivia {
+		self.trivia
+	}
+
+	fn parse_content(value: &mut CharStream<'src>) -> Result<(&'src str, Cow<'src, str>), ParseError> {
+		Q::Start::parse(value)
+			.map_err(|error| error.with_expected(vec![Expected::Named("multi-line string".to_owned())]))?;
+		// the delimiters are parsed on their own, a tuple would take the error that ended a repetition before the string, see [`CharStream::after_stop`].
+		Q::Start::parse(value)?;
+		Q::Start::parse(value)?;
+		let start = value.offset();
+
+		let leading = match &value.source()[start..] {
+			rest if rest.starts_with('\n') => 1,
+			rest if rest.starts_with("\r\n") => 2,
+			_ => 0
+		};
+		let mut decoded: Option<String> = None;
 
 		loop {
-			let item = match I::parse(value) {
-				Ok(value) => value,
-				Err(error) => {
-					if items.len() > 0 {
-						return Err(error);
+			let offset = value.offset();
+			match value.attempt(|value| Ok((Q::End::parse(value)?, Q::End::parse(value)?, Q::End::parse(value)?))) {
+				Ok(_) => {
+					// Like in TOML, up to two quotes right before the closing delimiter are part of the string.
+					let closing = value.offset() - offset;
+					for _ in 0..2 {
+						if Q::End::parse(value).is_err() {
+							break;
+						}
 					}
-					break
-				}
-			};
 
-			let separator = match S::parse(value) {
-				Ok(value) => Some(value),
-				_ => {
-					items.push((item, None));
-					break;
+					let end = value.offset() - closing;
+					if let Some(decoded) = &mut decoded {
+						decoded.push_str(&value.source()[offset..end]);
+					}
+
+					let raw = &value.source()[start..end];
+					let decoded = match decoded {
+						Some(decoded) => Cow::Owned(decoded),
+						None => Cow::Borrowed(&raw[leading.min(raw.len())..])
+					};
+					return Ok((raw, decoded));
 				}
-			};
+				Err(error) if value.at_end() => return Err(error),
+				Err(_) => {}
+			}
 
-			items.push((item, separator));
+			let escape_start = value.position();
+			match value.next() {
+				// a `\` is only a line continuation when nothing but spaces and tabs come after it on its line.
+				Some('\\') if value.source()[value.offset()..].trim_start_matches([' ', '\t']).starts_with(['\n', '\r']) => {
+					decoded.get_or_insert_with(|| value.source()[start + leading..offset].to_owned());
+					while value.peek().is_some_and(char::is_whitespace) {
+						value.next();
+					}
+				}
+				Some('\\') => {
+					let chr = escape(value, escape_start)?;
+					decoded.get_or_insert_with(|| value.source()[start + leading..offset].to_owned()).push(chr);
+				}
+				Some(chr) => if let Some(decoded) = &mut decoded {
+					decoded.push(chr);
+				}
+				None => unreachable!("the end of the input is checked before reading a character")
+			}
 		}
+	}
+}
 
-		let end = value.position();
-
-		Ok(Self { items, span: Span::new(start, end) })
-    }
+impl<'src, Q> Parse<'src> for TripleQuotedString<'src, Q> where Q: tokens::Delimiter {
+	fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized {
+		value.attempt(|value| {
+			let leading = value.leading_trivia();
+			let start = value.position();
+			let whitespace = value.whitespace();
+			value.set_whitespace(WhitespaceType::KeepAll);
+			let content = Self::parse_content(value);
+			value.set_whitespace(whitespace);
+			let (raw, inner_value) = content?;
+
+			let span = Span::new(start, value.position());
+			Ok(Self { raw, value: inner_value, span, trivia: Trivia::new(leading, value.trailing_trivia()), delimiter: PhantomData })
+		})
+	}
 
 	fn span(&self) -> Span {
-		self.span.clone()
+		self.span
 	}
 }
 
-impl<I, S> fmt::Debug for List<I, S> where 
-	I: Parse + fmt::Debug,
-	S: tokens::Token + fmt::Debug
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "List({:#?}, from {})", self.items, self.span())
+impl<Q> fmt::Debug for TripleQuotedString<'_, Q> where Q: tokens::Delimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "MultilineString({}, from {})", self.value, self.span)
     }
 }
 
-/// StringValue represents a string.
-/// this is necessary because it needs to store some additional information for the AST, like the info necessary for [`Parse::span`].
+/// The style of a [`BlockScalar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockStyle {
+	/// `|`, the line breaks are kept.
+	Literal,
+	/// `>`, lines are joined with spaces, and empty lines become line breaks.
+	Folded
+}
+
+/// What happens to the line breaks at the end of a [`BlockScalar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chomping {
+	/// No indicator, a single line break is kept.
+	Clip,
+	/// `-`, no line break is kept.
+	Strip,
+	/// `+`, all line breaks are kept.
+	Keep
+}
+
+/// A BlockScalar is a YAML block scalar, a string that starts with `|` or `>` and continues on the lines below,
+/// for as long as they are indented further than the line it started on.
+/// The header can have a chomping indicator (`-` or `+`) and an indentation indicator (`1` to `9`).
+///
+/// It works in any [`WhitespaceType`], and leaves the stream at the end of its last line, so an [`Indent`] around it sees the next line.
 /// ```
-/// # use parseal::parsing::{StringValue, Parse, charstream::CharStream};
+/// # use parseal::parsing::{BlockScalar, Identifier, Parse, charstream::CharStream, tokens};
 /// # fn main() {
-/// 	let mut buffer = CharStream::new("\"Hello, world!\"".to_owned()).build();
-/// 	let value = StringValue::parse(&mut buffer);
-/// 
-/// 	assert!(value.is_ok());
+/// 	let source = "script: |\n  echo one\n  echo two\n\nnext: >-\n  folded\n  text\n";
+/// 	let mut buffer = CharStream::new(source).build();
+///
+/// 	let (_, _, script) = <(Identifier, tokens::Colon, BlockScalar)>::parse(&mut buffer).unwrap();
+/// 	assert_eq!(script.value(), "echo one\necho two\n");
+///
+/// 	let (_, _, next) = <(Identifier, tokens::Colon, BlockScalar)>::parse(&mut buffer).unwrap();
+/// 	assert_eq!(next.value(), "folded text");
 /// # }
 /// ```
+#[allow(clippy::tabs_in_doc_comments)]
 #[derive(Clone)]
-pub struct StringValue {
-	delim: tokens::Quote,
-	value: String
+pub struct BlockScalar<'src> {
+	style: BlockStyle,
+	chomping: Chomping,
+	raw: &'src str,
+	value: String,
+	span: Span,
+	trivia: Trivia
 }
 
-impl Parse for StringValue {
-	fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized {
-		let left = <tokens::Quote as tokens::Delimiter>::Start::parse(value)?;
-		let mut inner_value = String::new();
+impl<'src> BlockScalar<'src> {
+	pub fn style(&self) -> BlockStyle {
+		self.style
+	}
+
+	pub fn chomping(&self) -> Chomping {
+		self.chomping
+	}
+
+	/// Returns the whole block scalar as it was written, including the header.
+	pub fn raw(&self) -> &'src str {
+		self.raw
+	}
 
-		let mut string_value = value.clone();
+	/// Returns the text of the block, without its indentation, folded and chomped as the header says.
+	pub fn value(&self) -> &str {
+		&self.value
+	}
 
-		let mut position = string_value.position();
+	/// Returns the trivia around the block, which is only recorded in a lossless stream.
+	/// The line break after the last line is leading trivia of whatever comes next.
+	pub fn trivia(&self) -> Trivia {
+		self.trivia
+	}
 
-		string_value.set_whitespace(WhitespaceType::KeepAll);
+	fn parse_block(value: &mut CharStream<'src>, style: BlockStyle, start: Position, leading: Span) -> Result<Self, ParseError> {
+		let mut chomping = Chomping::Clip;
+		let mut explicit_indent = None;
 		loop {
-			match string_value.next() {
-				Some(value) if value != '"' => {
-					inner_value.push(value);
-					position = string_value.position();
-				}
+			match value.peek() {
+				Some('-') if chomping == Chomping::Clip => chomping = Chomping::Strip,
+				Some('+') if chomping == Chomping::Clip => chomping = Chomping::Keep,
+				Some(chr @ '1'..='9') if explicit_indent.is_none() => explicit_indent = chr.to_digit(10).map(|indent| indent as usize),
 				_ => break
 			}
+			value.next();
+		}
+
+		while value.peek().is_some_and(|chr| chr == ' ' || chr == '\t') {
+			value.next();
 		}
 
-		value.goto(position)?;
-		
-		let right = <tokens::Quote as tokens::Delimiter>::End::parse(value)?;
+		let header_end = value.position();
+		match value.peek() {
+			None | Some('\n') | Some('\r') => {}
+			Some(chr) => {
+				value.next();
+				return Err(ParseError::unexpected(Expected::Named("line break".to_owned()), &chr.to_string(), Span::new(header_end, value.position())));
+			}
+		}
 
-		Ok(Self { delim: tokens::Delimiter::new(left, right), value: inner_value})
-    }
+		let source = value.source();
+		let parent = line_indentation(source, start.offset);
+		let mut block_indent = explicit_indent.map(|indent| parent + indent);
+
+		let mut pieces = source[header_end.offset..].split_inclusive('\n');
+		let mut offset = header_end.offset + pieces.next().map_or(0, str::len);
+		let mut end = header_end.offset;
+		let mut lines = Vec::new();
+		for piece in pieces {
+			let line = piece.trim_end_matches(&['\n', '\r'][..]);
+			if line.trim().is_empty() {
+				lines.push("");
+				offset += piece.len();
+				continue;
+			}
+
+			let indent = line.len() - line.trim_start_matches(' ').len();
+			let block_indent = *block_indent.get_or_insert(indent);
+			if indent <= parent || indent < block_indent {
+				break;
+			}
+
+			lines.push(&line[block_indent..]);
+			end = offset + line.len();
+			offset += piece.len();
+		}
+
+		let trailing = lines.iter().rev().take_while(|line| line.is_empty()).count();
+		lines.truncate(lines.len() - trailing);
+
+		let mut text = match style {
+			BlockStyle::Literal => lines.join("\n"),
+			BlockStyle::Folded => fold(&lines)
+		};
+		let has_break = source[end..].starts_with('\n') || source[end..].starts_with("\r\n");
+		match chomping {
+			Chomping::Keep if lines.is_empty() => text.push_str(&"\n".repeat(trailing)),
+			Chomping::Keep if has_break => text.push_str(&"\n".repeat(trailing + 1)),
+			Chomping::Clip if has_break && !lines.is_empty() => text.push('\n'),
+			_ => {}
+		}
+
+		while value.offset() < end {
+			value.next();
+		}
+
+		let span = Span::new(start, value.position());
+		Ok(Self { style, chomping, raw: &source[start.offset..end], value: text, span, trivia: Trivia::new(leading, Span::new(span.end, span.end)) })
+	}
+}
+
+impl<'src> Parse<'src> for BlockScalar<'src> {
+	fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized {
+		value.attempt(|value| {
+			let leading = value.leading_trivia();
+			let start = value.position();
+			let style = match value.next() {
+				Some('|') => BlockStyle::Literal,
+				Some('>') => BlockStyle::Folded,
+				Some(chr) => return Err(ParseError::unexpected(Expected::Named("block scalar".to_owned()), &chr.to_string(), Span::new(start, value.position()))),
+				None => return Err(ParseError::eof(Expected::Named("block scalar".to_owned()), value.position()))
+			};
+
+			let whitespace = value.whitespace();
+			value.set_whitespace(WhitespaceType::KeepAll);
+			let block = Self::parse_block(value, style, start, leading);
+			value.set_whitespace(whitespace);
+
+			let mut block = block?;
+			block.trivia.trailing = value.trailing_trivia();
+			Ok(block)
+		})
+	}
 
 	fn span(&self) -> Span {
-		self.delim.span()
+		self.span
 	}
 }
 
-impl fmt::Debug for StringValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "StringValue({}, from {})", self.value, self.span())
+impl fmt::Debug for BlockScalar<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "BlockScalar({:?}, from {})", self.value, self.span)
     }
 }
 
+/// Returns the number of spaces at the start of the line that byte `offset` is on.
+fn line_indentation(source: &str, offset: usize) -> usize {
+	let line_start = source[..offset].rfind('\n').map_or(0, |newline| newline + 1);
+	let line = &source[line_start..];
+	line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Joins the lines of a folded block scalar: lines next to each other are joined with a space,
+/// and every empty line becomes a line break. Lines that are indented further keep their line breaks.
+fn fold(lines: &[&str]) -> String {
+	let more_indented = |line: &str| line.starts_with(' ') || line.starts_with('\t');
+
+	let mut text = String::new();
+	for (i, line) in lines.iter().enumerate() {
+		if i > 0 {
+			let previous = lines[i - 1];
+			if line.is_empty() {
+				text.push('\n');
+			} else if !previous.is_empty() {
+				text.push(if more_indented(line) || more_indented(previous) { '\n' } else { ' ' });
+			}
+		}
+		text.push_str(line);
+	}
+	text
+}
+
+/// Decodes the escape sequence after a `\`, which started at `start`.
+fn escape(value: &mut CharStream, start: Position) -> Result<char, ParseError> {
+	let chr = match value.next() {
+		Some('"') => '"',
+		Some('\'') => '\'',
+		Some('\\') => '\\',
+		Some('/') => '/',
+		Some('n') => '\n',
+		Some('t') => '\t',
+		Some('r') => '\r',
+		Some('b') => '\u{8}',
+		Some('f') => '\u{c}',
+		Some('0') => '\0',
+		Some('u') => return unicode_escape(value, start),
+		Some(chr) => return Err(ParseError::unexpected(Expected::Named("escape sequence".to_owned()), &format!("\\{}", chr), Span::new(start, value.position()))),
+		None => return Err(ParseError::eof(Expected::Named("escape sequence".to_owned()), value.position()))
+	};
+
+	Ok(chr)
+}
+
+/// Decodes the digits of a `\u` escape, and the second half of a surrogate pair if the first one is a high surrogate.
+fn unicode_escape(value: &mut CharStream, start: Position) -> Result<char, ParseError> {
+	let high = hex_digits(value)?;
+	let code = match high {
+		0xD800..=0xDBFF => {
+			let checkpoint = value.checkpoint();
+			let low = match (value.next(), value.next()) {
+				(Some('\\'), Some('u')) => hex_digits(value)?,
+				_ => {
+					value.rewind(checkpoint);
+					0
+				}
+			};
+
+			match low {
+				0xDC00..=0xDFFF => 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00),
+				_ => high
+			}
+		}
+		code => code
+	};
+
+	char::from_u32(code).ok_or_else(|| {
+		let found = value.source()[start.offset..value.offset()].to_owned();
+		ParseError::invalid_value(vec![Expected::Named("unicode scalar value".to_owned())], Some(found), Span::new(start, value.position()))
+	})
+}
+
+fn hex_digits(value: &mut CharStream) -> Result<u32, ParseError> {
+	let mut code = 0;
+	for _ in 0..4 {
+		let start = value.position();
+		match value.next() {
+			Some(chr) if chr.is_ascii_hexdigit() => code = code * 16 + chr.to_digit(16).unwrap(),
+			Some(chr) => return Err(ParseError::unexpected(Expected::Named("hex digit".to_owned()), &chr.to_string(), Span::new(start, value.position()))),
+			None => return Err(ParseError::eof(Expected::Named("hex digit".to_owned()), value.position()))
+		}
+	}
+
+	Ok(code)
+}
+
 /// An Identifier represents things like words and names.
 /// ```
 /// # use parseal::parsing::{charstream::CharStream, Identifier, Parse, tokens, self};
 /// 
 /// # fn main() {
-/// 	let buffer = "hello world".to_owned();
+/// 	let buffer = "hello world";
 /// 	let mut buffer = CharStream::new(buffer).build();
 /// 	
 /// 	let value = Vec::<Identifier>::parse(&mut buffer).unwrap();
@@ -237,129 +1434,289 @@ impl fmt::Debug for StringValue {
 /// 	{
 /// 		# use parseal::Parsable;
 /// 		#[derive(Parsable, Clone)]
-/// 		enum Bool {
-/// 			True(#[value("true")] Identifier),
-/// 			False(#[value("false")] Identifier)
+/// 		enum Bool<'a> {
+/// 			True(#[value("true")] Identifier<'a>),
+/// 			False(#[value("false")] Identifier<'a>)
 /// 		}
 /// 
-/// 		let mut buffer = CharStream::new("true | false".to_owned()).build();
+/// 		let mut buffer = CharStream::new("true | false").build();
 /// 		let value = <(Bool, tokens::Pipe, Bool)>::parse(&mut buffer);
 /// 		assert!(value.is_ok());
 /// 	}
 /// # }
 /// ```
+#[allow(clippy::tabs_in_doc_comments)]
+pub type Identifier<'src> = Ident<'src, identifier::Alphanumeric>;
+
+/// An Ident is an identifier whose characters are decided by the [`identifier::IdentifierRules`] `R`.
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, identifier, Ident, Identifier, Parse};
+/// # fn main() {
+/// 	let mut buffer = CharStream::new("snake_case").build();
+/// 	assert_eq!(Identifier::parse(&mut buffer.clone()).unwrap().as_str(), "snake");
+/// 	assert_eq!(Ident::<identifier::Rust>::parse(&mut buffer).unwrap().as_str(), "snake_case");
+///
+/// 	let mut buffer = CharStream::new("--main-color").build();
+/// 	assert_eq!(Ident::<identifier::Css>::parse(&mut buffer).unwrap().as_str(), "--main-color");
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
 #[derive(Clone)]
-pub struct Identifier {
-	identifier: String,
-	span: Span
+pub struct Ident<'src, R> where R: identifier::IdentifierRules {
+	identifier: &'src str,
+	span: Span,
+	trivia: Trivia,
+	rules: PhantomData<R>
 }
 
-impl Parse for Identifier {
-	fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized {
-		let mut identifier = String::new();
-		let start = value.position();
-
-		let mut ident_value = value.clone();
-		match ident_value.next() {
-			Some(chr) if chr.is_alphabetic() => {
-				let mut position = ident_value.position();
-				identifier.push(chr);
-
-				ident_value.set_whitespace(WhitespaceType::KeepAll);
-
-				loop {
-					match ident_value.next() {
-						Some(value) if value.is_alphanumeric() => {
-							identifier.push(value);
-							position = ident_value.position();
-						}
-						_ => break
+impl<'src, R> Parse<'src> for Ident<'src, R> where R: identifier::IdentifierRules {
+	fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized {
+		value.attempt(|value| {
+			let leading = value.leading_trivia();
+			let start = value.position();
+			let start_offset = value.offset();
+			match value.next() {
+				Some(chr) if identifier::starts::<R>(chr) => {
+					let whitespace = value.whitespace();
+					value.set_whitespace(WhitespaceType::KeepAll);
+					while value.peek().filter(|chr| identifier::continues::<R>(*chr)).is_some() {
+						value.next();
 					}
+					value.set_whitespace(whitespace);
 				}
-
-				value.goto(position)?;
+				Some(chr) => return Err(ParseError::unexpected(Expected::Named("identifier".to_owned()), &chr.to_string(), Span::new(start, value.position()))),
+				None => return Err(ParseError::eof(Expected::Named("identifier".to_owned()), value.position()))
 			}
-			_ => return Err(ParseError("Did not find identifier".to_string(), ident_value.position()))
-		}
 
-		let end = value.position();
+			let identifier = value.slice_from(start_offset);
+			let end = value.position();
+
+			if R::RESERVED.contains(&identifier) {
+				return Err(ParseError::invalid_value(vec![Expected::Named("identifier".to_owned())], Some(identifier.to_owned()), Span::new(start, end)));
+			}
 
-		Ok(Self { identifier , span: Span::new(start, end)})
+			let trivia = Trivia::new(leading, value.trailing_trivia());
+			Ok(Self { identifier , span: Span::new(start, end), trivia, rules: PhantomData })
+		})
     }
 
 	fn span(&self) -> Span {
-		self.span.clone()
+		self.span
+	}
+}
+
+impl<'src, R> Ident<'src, R> where R: identifier::IdentifierRules {
+	pub fn as_str(&self) -> &'src str {
+		self.identifier
+	}
+
+	/// Returns the trivia around the identifier, which is only recorded in a lossless stream.
+	pub fn trivia(&self) -> Trivia {
+		self.trivia
 	}
 }
 
-impl fmt::Debug for Identifier {
+impl<R> fmt::Debug for Ident<'_, R> where R: identifier::IdentifierRules {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Identifier({}, from {})", self.identifier, self.span)
     }
 }
 
-impl PartialEq<&str> for Identifier {
+impl<R> PartialEq<&str> for Ident<'_, R> where R: identifier::IdentifierRules {
     fn eq(&self, other: &&str) -> bool {
-        self.identifier == other.to_owned()
+        self.identifier == *other
     }
 }
 
 /// A Number is a representation of a number, duh.
 /// this representation is needed since it needs to store some additional information for the AST.
+/// It accepts the numbers of the [`number::Lenient`] dialect, like `-12`, `.5`, `1e10`, `0x1F` and `1_000`.
 /// ```
 /// # use parseal::parsing::{Number, Parse, charstream::CharStream};
 /// # fn main() {
-/// 	let mut buffer = CharStream::new("69420".to_owned()).build();
+/// 	let mut buffer = CharStream::new("69420").build();
 /// 	let value = Number::parse(&mut buffer);
 /// 
 /// 	assert!(value.is_ok());
 /// # }
 /// ```
+#[allow(clippy::tabs_in_doc_comments)]
+pub type Number<'src> = NumberLiteral<'src, number::Lenient>;
+
+/// A NumberLiteral is a number that is written the way the dialect `D` allows.
+/// Only ASCII digits are digits, and the longest number the dialect accepts is taken.
+/// ```
+/// # use parseal::parsing::{NumberLiteral, number::{self, NumberKind}, Parse, charstream::CharStream};
+/// # fn main() {
+/// 	let mut buffer = CharStream::new("-6.626e-34").build();
+/// 	let value = NumberLiteral::<number::Json>::parse(&mut buffer).unwrap();
+/// 	assert_eq!(value.kind(), NumberKind::Float);
+///
+/// 	let mut buffer = CharStream::new("0xFF_u8").build();
+/// 	let value = NumberLiteral::<number::Rust>::parse(&mut buffer).unwrap();
+/// 	assert_eq!((value.radix(), value.suffix()), (16, "u8"));
+///
+/// 	// JSON has no leading zeros, so only the `0` is a number here.
+/// 	let mut buffer = CharStream::new("012").build();
+/// 	let value = NumberLiteral::<number::Json>::parse(&mut buffer).unwrap();
+/// 	assert_eq!(value.text(), "0");
+///
+/// 	// only TOML has `inf` and `nan`, for the other dialects they are words like any other.
+/// 	let mut buffer = CharStream::new("-inf").build();
+/// 	let value = NumberLiteral::<number::Toml>::parse(&mut buffer).unwrap();
+/// 	assert_eq!(value.as_f64().unwrap(), f64::NEG_INFINITY);
+/// 	assert!(NumberLiteral::<number::Lenient>::parse(&mut CharStream::new("inf").build()).is_err());
+/// 	assert!(NumberLiteral::<number::Lenient>::parse(&mut CharStream::new("-nan").build()).is_err());
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
 #[derive(Clone)]
-pub struct Number {
-	value: String,
-	span: Span
+pub struct NumberLiteral<'src, D> where D: number::NumberDialect {
+	value: &'src str,
+	kind: number::NumberKind,
+	radix: u32,
+	suffix: usize,
+	span: Span,
+	trivia: Trivia,
+	dialect: PhantomData<D>
 }
 
-impl Parse for Number {
-	fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized {
-		let mut number = String::new();
-		let start = value.position();
-		
-		let mut num_value = value.clone();
-		match num_value.next() {
-			Some(chr) if chr.is_numeric() => {
-				let mut position = num_value.position();
-				number.push(chr);
-
-				num_value.set_whitespace(WhitespaceType::KeepAll);
-
-				loop {
-					match num_value.next() {
-						Some(value) if value.is_numeric() => {
-							number.push(value);
-							position = num_value.position();
-						}
-						_ => break
-					}
-				}
+impl<'src, D> NumberLiteral<'src, D> where D: number::NumberDialect {
+	/// Returns the number as it was written, including its sign, radix prefix and suffix.
+	pub fn text(&self) -> &'src str {
+		self.value
+	}
 
-				value.goto(position)?;
-			}
-			_ => return Err(ParseError("Did not find number".to_string(), num_value.position()))
+	pub fn kind(&self) -> number::NumberKind {
+		self.kind
+	}
+
+	pub fn radix(&self) -> u32 {
+		self.radix
+	}
+
+	/// Returns the type suffix of the number, or an empty string if it has none.
+	pub fn suffix(&self) -> &'src str {
+		&self.value[self.value.len() - self.suffix..]
+	}
+
+	/// Returns the trivia around the number, which is only recorded in a lossless stream.
+	pub fn trivia(&self) -> Trivia {
+		self.trivia
+	}
+
+	/// Converts the number to an `i64`.
+	/// It fails with an error at the span of the number if it is a float, or does not fit.
+	/// ```
+	/// # use parseal::parsing::{Number, Parse, charstream::CharStream};
+	/// # fn main() {
+	/// 	let mut buffer = CharStream::new("-0x7F 1_000 300 2.5").build();
+	/// 	let numbers = <[Number; 4]>::parse(&mut buffer).unwrap();
+	///
+	/// 	assert_eq!(numbers[0].as_i64().unwrap(), -127);
+	/// 	assert_eq!(numbers[1].as_u64().unwrap(), 1000);
+	/// 	assert_eq!(numbers[3].as_f64().unwrap(), 2.5);
+	///
+	/// 	let error = numbers[2].as_integer::<u8>().unwrap_err();
+	/// 	assert_eq!(error.message(), "expected number that fits in u8, found `300`");
+	/// 	assert_eq!(error.span(), &numbers[2].span());
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn as_i64(&self) -> Result<i64, ParseError> {
+		self.as_integer()
+	}
+
+	pub fn as_u64(&self) -> Result<u64, ParseError> {
+		self.as_integer()
+	}
+
+	pub fn as_i128(&self) -> Result<i128, ParseError> {
+		self.as_integer()
+	}
+
+	/// Converts the number to any integer type that an `i128` can be converted to.
+	pub fn as_integer<T>(&self) -> Result<T, ParseError> where T: TryFrom<i128> {
+		if self.kind == number::NumberKind::Float {
+			return Err(self.conversion_error("integer"));
+		}
+
+		let (sign, digits) = self.digits();
+		let digits = match self.radix {
+			10 => digits,
+			_ => digits[2..].to_owned()
+		};
+
+		i128::from_str_radix(&format!("{}{}", sign, digits), self.radix)
+			.ok()
+			.and_then(|value| T::try_from(value).ok())
+			.ok_or_else(|| self.conversion_error(&format!("number that fits in {}", std::any::type_name::<T>())))
+	}
+
+	/// Converts the number to an `f64`, which works for integers as well.
+	/// It fails if the number is too large to be represented as anything other than infinity.
+	pub fn as_f64(&self) -> Result<f64, ParseError> {
+		if self.kind == number::NumberKind::Integer && self.radix != 10 {
+			return self.as_i128().map(|value| value as f64);
+		}
+
+		let (sign, digits) = self.digits();
+		let special = D::SPECIAL.contains(&digits.as_str());
+		match format!("{}{}", sign, digits).parse::<f64>() {
+			Ok(value) if value.is_finite() || special => Ok(value),
+			_ => Err(self.conversion_error("number that fits in f64"))
 		}
+	}
+
+	/// Returns the sign of the number and its digits, without the separators and the suffix.
+	fn digits(&self) -> (&'src str, String) {
+		let text = &self.value[..self.value.len() - self.suffix];
+		let (sign, text) = match text.strip_prefix(D::SIGNS) {
+			Some(rest) => text.split_at(text.len() - rest.len()),
+			None => ("", text)
+		};
+
+		(sign, text.chars().filter(|chr| Some(*chr) != D::SEPARATOR).collect())
+	}
+
+	fn conversion_error(&self, expected: &str) -> ParseError {
+		ParseError::invalid_value(vec![Expected::Named(expected.to_owned())], Some(self.value.to_owned()), self.span)
+	}
+}
+
+impl<'src, D> Parse<'src> for NumberLiteral<'src, D> where D: number::NumberDialect {
+	fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized {
+		value.attempt(|value| {
+			let leading = value.leading_trivia();
+			let start = value.position();
+			let start_offset = value.offset();
+
+			let scanned = match number::scan::<D>(&value.source()[start_offset..]) {
+				Some(scanned) => scanned,
+				None => return Err(match value.next() {
+					Some(chr) => ParseError::unexpected(Expected::Named("number".to_owned()), &chr.to_string(), Span::new(start, value.position())),
+					None => ParseError::eof(Expected::Named("number".to_owned()), value.position())
+				})
+			};
+
+			while value.offset() < start_offset + scanned.len {
+				value.next();
+			}
 
-		let end = value.position();
+			let number = value.slice_from(start_offset);
+			let end = value.position();
 
-		Ok(Number { value: number, span: Span::new(start, end)})
+			let trivia = Trivia::new(leading, value.trailing_trivia());
+			Ok(Self { value: number, kind: scanned.kind, radix: scanned.radix, suffix: scanned.suffix, span: Span::new(start, end), trivia, dialect: PhantomData })
+		})
     }
 
 	fn span(&self) -> Span {
-		self.span.clone()
+		self.span
 	}
 }
 
-impl fmt::Debug for Number {
+impl<D> fmt::Debug for NumberLiteral<'_, D> where D: number::NumberDialect {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Number({}, from {})", self.value, self.span)
     }
@@ -371,31 +1728,47 @@ pub struct Indent<T> {
 	depth: u8
 }
 
-impl<T> Parse for Indent<T> where T: Parse {
-    fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized {
+impl<'src, T> Parse<'src> for Indent<T> where T: Parse<'src> {
+    fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized {
         let mut values = Vec::new();
 
-		let mut indent_value = value.clone();
-		indent_value.set_whitespace(WhitespaceType::Indent);
-		let mut position = indent_value.position();
+		let whitespace = value.whitespace();
+		value.set_whitespace(WhitespaceType::Indent);
 
-		let mut item = T::parse(&mut indent_value);
-		let depth= indent_value.indent();
-		while item.is_ok() {
-			position = indent_value.position();
-			values.push(item?);
-			item = T::parse(&mut indent_value);
+		let checkpoint = value.checkpoint();
+		value.skip_whitespace();
+		let depth = value.indent();
 
-			if indent_value.indent() != depth {
-				break;
+		let result = loop {
+			let item_start = value.checkpoint();
+			value.skip_whitespace();
+			if !values.is_empty() && value.indent() != depth {
+				value.rewind(item_start);
+				break Ok(Self { values, depth });
 			}
-		}
 
-		if values.len() == 0 {
-			Err(ParseError("Could not find Indent block.".to_string(), position))
-		} else {
-			Ok(Self { values, depth })
-		}
+			let offset = value.offset();
+			match value.attempt(T::parse) {
+				// an item that consumed nothing would be parsed again forever, like `()`.
+				Ok(item) if value.offset() == offset => {
+					values.push(item);
+					break Ok(Self { values, depth });
+				}
+				Ok(item) => values.push(item),
+				Err(error) if values.is_empty() => {
+					value.rewind(checkpoint);
+					break Err(error);
+				}
+				Err(error) => {
+					value.rewind(item_start);
+					value.stop(error);
+					break Ok(Self { values, depth });
+				}
+			}
+		};
+
+		value.set_whitespace(whitespace);
+		result
     }
 
     fn span(&self) -> Span {
@@ -403,46 +1776,96 @@ impl<T> Parse for Indent<T> where T: Parse {
     }
 }
 
-impl<T> fmt::Debug for Indent<T> where T: fmt::Debug + Parse {
+impl<T> Indent<T> {
+	pub fn iter(&self) -> std::slice::Iter<'_, T> {
+		self.values.iter()
+	}
+
+	pub fn len(&self) -> usize {
+		self.values.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.values.is_empty()
+	}
+
+	/// Returns the indentation of the items, in the columns that [`WhitespaceType::Indent`] counts.
+	pub fn depth(&self) -> u8 {
+		self.depth
+	}
+
+	pub fn into_vec(self) -> Vec<T> {
+		self.values
+	}
+}
+
+impl<T> IntoIterator for Indent<T> {
+	type Item = T;
+	type IntoIter = std::vec::IntoIter<T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.values.into_iter()
+	}
+}
+
+impl<'a, T> IntoIterator for &'a Indent<T> {
+	type Item = &'a T;
+	type IntoIter = std::slice::Iter<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.values.iter()
+	}
+}
+
+impl<'src, T> fmt::Debug for Indent<T> where T: fmt::Debug + Parse<'src> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(f, "Indent({:#?}, from {}, depth {})", self.values, self.span(), self.depth)
     }
 }
 
-impl<T> Parse for Vec<T> where T: Parse {
-	fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized {
+impl<'src, T> Parse<'src> for Vec<T> where T: Parse<'src> {
+	fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized {
 		let mut vec = Vec::new();
 
-		let mut item = T::parse(value);
-		while item.is_ok() {
-			vec.push(item?);
-			item = T::parse(value);
-		}
+		let error = loop {
+			let offset = value.offset();
+			match value.attempt(T::parse) {
+				// an item that consumed nothing would be parsed again forever, like `()`.
+				Ok(inner) if value.offset() == offset => {
+					vec.push(inner);
+					return Ok(vec);
+				}
+				Ok(inner) => vec.push(inner),
+				Err(error) => break error
+			}
+		};
 
-		if vec.len() == 0 {
-			Err(ParseError("Could not find vector.".to_string(), value.position()))
-		} else {
-			Ok(vec)
+		if vec.is_empty() {
+			return Err(error);
 		}
+		value.stop(error);
+		Ok(vec)
 	}
 
 	fn span(&self) -> Span {
-		Span::new(self.first().unwrap().span().start, self.last().unwrap().span().start)
+		Span::new(self.first().unwrap().span().start, self.last().unwrap().span().end)
 	}
 }
 
-impl<T, const N: usize> Parse for [T; N] where T: Parse + fmt::Debug {
-    fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized {
-        let mut result = Vec::new();
+impl<'src, T, const N: usize> Parse<'src> for [T; N] where T: Parse<'src> + fmt::Debug {
+    fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized {
+        value.attempt(|value| {
+			let mut result = Vec::new();
 
-		for _ in 0..N {
-			result.push(T::parse(value)?);
-		}
+			for _ in 0..N {
+				result.push(T::parse(value)?);
+			}
 
-		match <[T; N]>::try_from(result) {
-			Ok(result) => Ok(result),
-			Err(error) => Err(ParseError(format!("Could not create slice from parsed values. \nvalues where: {:?}", error), value.position()))
-		}
+			match <[T; N]>::try_from(result) {
+				Ok(result) => Ok(result),
+				Err(error) => Err(ParseError::new(&format!("Could not create slice from parsed values. \nvalues where: {:?}", error), value.position()))
+			}
+		})
     }
 
 	fn span(&self) -> Span {
@@ -450,37 +1873,85 @@ impl<T, const N: usize> Parse for [T; N] where T: Parse + fmt::Debug {
 	}
 }
 
-//TODO: see if this can be more general
-impl<A, B> Parse for (A, B) where
-	A: Parse,
-	B: Parse
-{
-    fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized {
-        Ok((
-			A::parse(value)?,
-			B::parse(value)?
-		))
-    }
-	
+/// `()` matches nothing and always succeeds, without consuming any input.
+/// It has no position, so its span is [`Span::DETACHED`], which [`Span::join`] leaves out of the span of a sequence.
+/// A repetition of `()`, like `Vec<()>`, stops after the first item, since the items after it would not consume anything either.
+/// ```
+/// # use parseal::parsing::{charstream::{CharStream, Span}, Parse, Indent, Number};
+/// # fn main() {
+/// 	assert_eq!(Vec::<()>::parse(&mut CharStream::new("1 2").build()).unwrap().len(), 1);
+/// 	assert_eq!(Indent::<()>::parse(&mut CharStream::new("1 2").build()).unwrap().len(), 1);
+///
+/// 	let value = <((), ())>::parse(&mut CharStream::new("1 2").build()).unwrap();
+/// 	assert_eq!(value.span(), Span::DETACHED);
+///
+/// 	#[cfg(feature="derive")]
+/// 	{
+/// 		# use parseal::{Parsable, parsing};
+/// 		#[derive(Parsable, Debug, Clone)]
+/// 		struct Trailing<'a> {
+/// 			number: Number<'a>,
+/// 			nothing: ()
+/// 		}
+///
+/// 		let value = Trailing::parse(&mut CharStream::new("   42").build()).unwrap();
+/// 		assert_eq!(value.span().range(), 3..5);
+/// 	}
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+impl<'src> Parse<'src> for () {
+	fn parse(_value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized {
+		Ok(())
+	}
+
 	fn span(&self) -> Span {
-		Span::new(self.0.span().start, self.1.span().end)
+		Span::DETACHED
 	}
 }
 
-impl<A, B, C> Parse for (A, B, C) where
-	A: Parse,
-	B: Parse,
-	C: Parse
-{
-    fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized {
-        Ok((
-			A::parse(value)?,
-			B::parse(value)?,
-			C::parse(value)?
-		))
-    }
+/// Implements Parse for a tuple, which parses its elements in order and backtracks if one of them fails.
+/// Tuples of up to 12 elements are implemented, so short sequences do not need a named struct.
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, tokens, Identifier, Number, Parse};
+/// type Field<'a> = (Identifier<'a>, tokens::Colon, Identifier<'a>, tokens::Equal, Number<'a>, ());
+///
+/// # fn main() {
+/// 	let (name, _, kind, _, value, ()) = Field::parse(&mut CharStream::new("  port: u16 = 8080 ").build()).unwrap();
+/// 	assert_eq!((name.as_str(), kind.as_str(), value.text()), ("port", "u16", "8080"));
+///
+/// 	let field = Field::parse(&mut CharStream::new("  port: u16 = 8080 ").build()).unwrap();
+/// 	assert_eq!(field.span().range(), 2..18);
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+macro_rules! parse_tuple {
+	($($name:ident $index:tt),+) => {
+		impl<'src, $($name),+> Parse<'src> for ($($name,)+) where
+			$($name: Parse<'src>),+
+		{
+			fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> where Self: Sized {
+				value.attempt(|value| Ok((
+					$(value.after_stop($name::parse)?,)+
+				)))
+			}
 
-	fn span(&self) -> Span {
-		Span::new(self.0.span().start, self.2.span().end)
-	}
+			fn span(&self) -> Span {
+				Span::join(&[$(self.$index.span()),+])
+			}
+		}
+	};
 }
+
+parse_tuple!(A 0);
+parse_tuple!(A 0, B 1);
+parse_tuple!(A 0, B 1, C 2);
+parse_tuple!(A 0, B 1, C 2, D 3);
+parse_tuple!(A 0, B 1, C 2, D 3, E 4);
+parse_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+parse_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+parse_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+parse_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+parse_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+parse_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+parse_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
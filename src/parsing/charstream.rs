@@ -1,74 +1,112 @@
-use std::{vec::IntoIter, fmt};
-use rand::random;
+use std::{cell::OnceCell, fmt, ops::Range, rc::Rc};
 
-use super::ParseError;
+use super::{ParseError, diagnostic::Diagnostics};
 
-#[derive(Clone, Debug, PartialEq)]
+/// A Position is a byte offset into a source, together with the id of that source.
+/// The line and column of a position are not stored, they are resolved on demand with a [`LineIndex`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Position {
-	pub column: usize,
-	pub	row: usize,
-	pub	index: usize,
-	pub	file: Option<String>,
-	pub file_id: u32
+	pub offset: usize,
+	pub source_id: u32
 }
 
 impl Position {
-	pub fn end(value: &str, file: Option<String>, file_id: u32) -> Position {
-		let mut column = 0;
-		let mut row = 0;
-
-		for c in value.chars() {
-			match c {
-				'\n' => {
-					column = 0;
-					row += 1;
-				}
-				_ => column += 1,
-			}
-		}
-
-		Self { column, row, index: value.len(), file, file_id }
+	pub fn new(offset: usize, source_id: u32) -> Self {
+		Self { offset, source_id }
 	}
 }
 
 impl PartialOrd for Position {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-		match self.file.partial_cmp(&other.file) {
-			Some(core::cmp::Ordering::Equal) => {}
-			_ => return None,
-		}
-		match self.file_id.partial_cmp(&other.file_id) {
-			Some(core::cmp::Ordering::Equal) => {}
-			_ => return None,
+		if self.source_id != other.source_id {
+			return None;
 		}
 
-        match self.row.partial_cmp(&other.row) {
-            Some(core::cmp::Ordering::Equal) => {}
-            ord => return ord,
-        }
-        self.index.partial_cmp(&other.index)
+        self.offset.partial_cmp(&other.offset)
     }
 }
 
+/// A position is shown as its byte offset, since resolving its line and column needs the source.
+/// [`CharStream::line_col`] and [`SourceMap::resolve`](super::source::SourceMap::resolve) do that.
 impl fmt::Display for Position {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		match &self.file {
-			Some(file) => write!(f,"{}:{}:{}", file, self.row, self.column),
-			None => write!(f, "{}:{}", self.row, self.column)
-		}
+		write!(f, "byte {}", self.offset)
 	}
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Span {
 	pub start: Position,
 	pub end: Position
 }
 
 impl Span {
+	/// An empty span that is not in any source, for values that have no position of their own, like `()`.
+	pub const DETACHED: Span = Span {
+		start: Position { offset: 0, source_id: u32::MAX },
+		end: Position { offset: 0, source_id: u32::MAX }
+	};
+
 	pub fn new(start: Position, end: Position) -> Self {
 		Self { start, end }
 	}
+
+	/// Joins the spans of the parts of a sequence, from the start of the first to the end of the last.
+	/// Empty spans, like the span of an empty [`List`](super::List), are left out.
+	/// If all spans are empty, the first one that is not [`Span::DETACHED`] is returned.
+	/// ```
+	/// # use parseal::parsing::charstream::{Position, Span};
+	/// # fn main() {
+	/// 	let word = Span::new(Position::new(3, 0), Position::new(5, 0));
+	/// 	let empty = Span::new(Position::new(6, 0), Position::new(6, 0));
+	///
+	/// 	assert_eq!(Span::join(&[word, empty, Span::DETACHED]), word);
+	/// 	assert_eq!(Span::join(&[Span::DETACHED, empty]), empty);
+	/// 	assert_eq!(Span::join(&[Span::DETACHED]), Span::DETACHED);
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn join(spans: &[Span]) -> Span {
+		let mut filled = spans.iter().filter(|span| span.start != span.end);
+		match (filled.next(), filled.next_back()) {
+			(Some(first), Some(last)) => Span::new(first.start, last.end),
+			(Some(only), None) => *only,
+			_ => spans.iter().copied().find(|span| *span != Span::DETACHED).unwrap_or(Span::DETACHED)
+		}
+	}
+
+	/// Returns the byte range of the span in its source.
+	pub fn range(&self) -> Range<usize> {
+		self.start.offset..self.end.offset
+	}
+}
+
+/// The trivia of a token: the whitespace, line breaks and comments before and after it.
+/// It is only recorded when the stream is lossless, see [`CharStreamBuilder::lossless`], otherwise both spans are empty.
+///
+/// The trailing trivia runs up to the end of the line the token is on, the trivia after that is the leading trivia of the next token.
+/// Together with the text of the tokens, the trivia covers the source without gaps or overlap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Trivia {
+	pub leading: Span,
+	pub trailing: Span
+}
+
+impl Trivia {
+	pub fn new(leading: Span, trailing: Span) -> Self {
+		Self { leading, trailing }
+	}
+}
+
+/// A piece of trivia, as split up by [`CharStream::trivia_pieces`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriviaPiece<'src> {
+	/// Whitespace within a line.
+	Whitespace(&'src str),
+	/// A line break, `\n` or `\r\n`.
+	Newline(&'src str),
+	/// A line or block comment, including its delimiters.
+	Comment(&'src str)
 }
 
 impl PartialOrd for Span {
@@ -83,152 +121,699 @@ impl PartialOrd for Span {
 
 impl fmt::Display for Span {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "{} - {}:{}", self.start, self.end.row, self.end.column)
+		write!(f, "bytes {}..{}", self.start.offset, self.end.offset)
 	}
 }
 
-#[derive(Debug, Clone)]
+/// A line and column in a source, both counted from 0.
+/// Depending on how it was resolved, the column counts characters or UTF-16 code units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+	pub line: usize,
+	pub column: usize
+}
+
+impl fmt::Display for LineCol {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}:{}", self.line + 1, self.column + 1)
+	}
+}
+
+/// A LineIndex stores where every line of a source starts, so the line and column of a byte offset can be looked up quickly.
+/// It does not keep the source itself, so the source has to be passed to the lookups that need to count columns.
+/// ```
+/// # use parseal::parsing::charstream::{LineIndex, LineCol};
+/// # fn main() {
+/// 	let source = "first\nsecond 😀 line";
+/// 	let index = LineIndex::new(source);
+///
+/// 	let offset = source.find("line").unwrap();
+/// 	assert_eq!(index.line_col(source, offset), LineCol { line: 1, column: 9 });
+/// 	assert_eq!(index.line_col_utf16(source, offset), LineCol { line: 1, column: 10 });
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+	line_starts: Vec<usize>
+}
+
+impl LineIndex {
+	pub fn new(source: &str) -> Self {
+		let line_starts = std::iter::once(0)
+			.chain(source.match_indices('\n').map(|(offset, _)| offset + 1))
+			.collect();
+
+		Self { line_starts }
+	}
+
+	pub fn line_count(&self) -> usize {
+		self.line_starts.len()
+	}
+
+	/// Returns the line that byte `offset` is on.
+	pub fn line(&self, offset: usize) -> usize {
+		self.line_starts.partition_point(|start| *start <= offset) - 1
+	}
+
+	/// Returns the byte range of `line`, without the line break at its end.
+	pub fn line_range(&self, source: &str, line: usize) -> Range<usize> {
+		let start = self.line_starts.get(line).copied().unwrap_or(source.len());
+		let end = match self.line_starts.get(line + 1) {
+			Some(next) => next - 1,
+			None => source.len()
+		};
+
+		start..end.max(start)
+	}
+
+	/// Returns the line and column of byte `offset`, with the column counted in characters.
+	pub fn line_col(&self, source: &str, offset: usize) -> LineCol {
+		let line = self.line(offset);
+		let column = source[self.line_starts[line]..offset].chars().count();
+		LineCol { line, column }
+	}
+
+	/// Returns the line and column of byte `offset`, with the column counted in UTF-16 code units, like the Language Server Protocol does.
+	pub fn line_col_utf16(&self, source: &str, offset: usize) -> LineCol {
+		let line = self.line(offset);
+		let column = source[self.line_starts[line]..offset].encode_utf16().count();
+		LineCol { line, column }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum WhitespaceType {
 	Ignore,
 	KeepAll,
 	Indent
 }
 
-pub struct CharStreamBuilder {
-	buffer: String,
+/// A comment syntax, that is skipped wherever whitespace is skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Comment {
+	Line(String),
+	Block { open: String, close: String, nested: bool }
+}
+
+impl Comment {
+	/// Returns the length of this comment if one starts at the start of `text`.
+	/// A line comment ends before the line break, a block comment that is not closed runs to the end of `text`.
+	fn len(&self, text: &str) -> Option<usize> {
+		match self {
+			Self::Line(prefix) if text.starts_with(prefix.as_str()) => Some(text.find('\n').unwrap_or(text.len())),
+			Self::Block { open, close, nested } if text.starts_with(open.as_str()) => {
+				let nested = *nested && open != close;
+				let mut depth = 0;
+				let mut index = 0;
+				while let Some(chr) = text[index..].chars().next() {
+					let rest = &text[index..];
+					if (depth == 0 || nested) && rest.starts_with(open.as_str()) {
+						depth += 1;
+						index += open.len();
+					} else if rest.starts_with(close.as_str()) {
+						depth -= 1;
+						index += close.len();
+						if depth == 0 {
+							return Some(index);
+						}
+					} else {
+						index += chr.len_utf8();
+					}
+				}
+				Some(text.len())
+			}
+			_ => None
+		}
+	}
+}
+
+pub struct CharStreamBuilder<'src> {
+	buffer: &'src str,
 	file: Option<String>,
-	file_id: u32,
-	indent_size: u8
+	source_id: u32,
+	indent_size: u8,
+	recover: bool,
+	diagnostics: Diagnostics,
+	comments: Vec<Comment>,
+	lossless: bool,
+	tokens: Vec<String>
 }
 
-impl CharStreamBuilder {
-	pub fn new(buffer: String) -> Self {
-		Self { buffer, file: None, file_id: random(), indent_size: 4 }
+impl<'src> CharStreamBuilder<'src> {
+	pub fn new(buffer: &'src str) -> Self {
+		Self { buffer, file: None, source_id: 0, indent_size: 4, recover: false, diagnostics: Diagnostics::new(), comments: Vec::new(), lossless: false, tokens: Vec::new() }
 	}
 
-	pub fn build(&mut self) -> CharStream {
-		let buffer = self.buffer.clone();
-		let chars = buffer.chars().collect::<Vec<_>>().into_iter();
-		let file = self.file.clone();
-		let eof = Position::end(&buffer, file.clone(), self.file_id);
+	/// Sets the name of the file that the buffer was read from.
+	pub fn file(&mut self, file: &str) -> &mut Self {
+		self.file = Some(file.to_owned());
+		self
+	}
+
+	/// Sets the id of the source, which is stored in every [`Position`].
+	/// A [`super::source::SourceMap`] sets this to the id it handed out for the source.
+	/// Streams that are not created through a source map have the id 0, which a source map never hands out.
+	pub fn source_id(&mut self, source_id: u32) -> &mut Self {
+		self.source_id = source_id;
+		self
+	}
 
+	/// Enables recovery mode.
+	/// In recovery mode, [`super::List`] and [`super::Group`] skip items that fail to parse,
+	/// record the error and keep going, instead of aborting the whole parse.
+	/// The recorded errors can be read from [`CharStream::diagnostics`].
+	pub fn recover(&mut self, recover: bool) -> &mut Self {
+		self.recover = recover;
+		self
+	}
+
+	/// Makes the stream report into an existing sink, so the diagnostics of multiple streams can be collected in one place.
+	pub fn diagnostics(&mut self, diagnostics: Diagnostics) -> &mut Self {
+		self.diagnostics = diagnostics;
+		self
+	}
+
+	/// Adds a line comment, which starts with `prefix` and runs to the end of the line, like `//` or `#`.
+	/// Comments are skipped wherever whitespace is skipped.
+	/// ```
+	/// # use parseal::parsing::{charstream::CharStream, tokens, Parse, Number, List, Group};
+	/// # fn main() {
+	/// 	let source = "[ // the answer\n 42, /* the other one */ 69 ]";
+	/// 	let mut buffer = CharStream::new(source).line_comment("//").block_comment("/*", "*/").build();
+	///
+	/// 	let value = Group::<tokens::Bracket, List<Number, tokens::Comma>>::parse(&mut buffer).unwrap();
+	/// 	assert_eq!(value.inner().unwrap().len(), 2);
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn line_comment(&mut self, prefix: &str) -> &mut Self {
+		if !prefix.is_empty() {
+			self.comments.push(Comment::Line(prefix.to_owned()));
+		}
+		self
+	}
+
+	/// Adds a block comment between `open` and `close`, like `/* */` or `<!-- -->`.
+	/// The comment ends at the first `close`, a comment that is not closed runs to the end of the source.
+	///
+	/// In [`WhitespaceType::Indent`] mode the indentation of a line is the whitespace before its first character, also when that is the end of a comment.
+	/// ```
+	/// # use parseal::parsing::{charstream::CharStream, tokens, Parse, Identifier, Number, Indent};
+	/// # fn main() {
+	/// 	let source = "x:\n  a: 1\n  /* the second\n  item */ b: 2";
+	/// 	let mut buffer = CharStream::new(source).block_comment("/*", "*/").build();
+	///
+	/// 	let value = Indent::<(Identifier, tokens::Colon, Indent<(Identifier, tokens::Colon, Number)>)>::parse(&mut buffer).unwrap();
+	/// 	let (_, _, inner) = value.iter().next().unwrap();
+	/// 	assert_eq!(inner.len(), 2);
+	/// 	assert_eq!(inner.depth(), 2);
+	/// 	assert_eq!(buffer.offset(), source.len());
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn block_comment(&mut self, open: &str, close: &str) -> &mut Self {
+		if !open.is_empty() {
+			self.comments.push(Comment::Block { open: open.to_owned(), close: close.to_owned(), nested: false });
+		}
+		self
+	}
+
+	/// Adds a block comment between `open` and `close` that can contain other comments of the same kind, like the `/* */` of Rust.
+	/// ```
+	/// # use parseal::parsing::{charstream::CharStream, Parse, Identifier};
+	/// # fn main() {
+	/// 	let mut buffer = CharStream::new("/* outer /* inner */ still outer */ name").nested_block_comment("/*", "*/").build();
+	/// 	assert_eq!(Identifier::parse(&mut buffer).unwrap().as_str(), "name");
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn nested_block_comment(&mut self, open: &str, close: &str) -> &mut Self {
+		if !open.is_empty() {
+			self.comments.push(Comment::Block { open: open.to_owned(), close: close.to_owned(), nested: true });
+		}
+		self
+	}
+
+	/// Enables lossless mode, in which every token records its [`Trivia`], so a parsed tree can be reprinted byte for byte.
+	/// ```
+	/// # use parseal::parsing::{charstream::CharStream, print::Printer, tokens, Parse, Identifier, Number};
+	/// # fn main() {
+	/// 	let source = "  name =\t42 // the answer\n";
+	/// 	let mut buffer = CharStream::new(source).line_comment("//").lossless(true).build();
+	///
+	/// 	let value = <(Identifier, tokens::Equal, Number)>::parse(&mut buffer).unwrap();
+	/// 	assert_eq!(&source[value.0.trivia().leading.range()], "  ");
+	/// 	assert_eq!(&source[value.2.trivia().trailing.range()], " // the answer");
+	///
+	/// 	assert_eq!(Printer::new().lossless(source).print(&value), source);
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn lossless(&mut self, lossless: bool) -> &mut Self {
+		self.lossless = lossless;
+		self
+	}
+
+	/// Registers tokens that are declared outside the [`create_tokens!`](crate::create_tokens) invocation of a token,
+	/// so a token that matches the longest token refuses to match when one of these is longer and matches as well.
+	/// This is how a token of a format, like `->`, keeps [`tokens::Hyphen`](super::tokens::Hyphen) from matching its start.
+	/// ```
+	/// # use parseal::{create_tokens, parsing::{charstream::CharStream, tokens, Parse}};
+	/// create_tokens! {
+	///     -> Arrow
+	/// }
+	///
+	/// # fn main() {
+	/// 	assert!(tokens::Hyphen::parse(&mut CharStream::new("->").build()).is_ok());
+	/// 	assert!(tokens::Hyphen::parse(&mut CharStream::new("->").tokens(&["->"]).build()).is_err());
+	/// 	assert!(Arrow::parse(&mut CharStream::new("->").tokens(&["->"]).build()).is_ok());
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn tokens(&mut self, tokens: &[&str]) -> &mut Self {
+		self.tokens.extend(tokens.iter().filter(|token| !token.is_empty()).map(|token| token.to_string()));
+		self
+	}
+
+	pub fn build(&mut self) -> CharStream<'src> {
 		CharStream { 
-			chars, 
-			file, 
-			file_id: self.file_id, 
-			column: 0, 
-			row: 0, 
-			index: 0, 
-			eof, 
+			source: self.buffer, 
+			offset: 0, 
+			file: self.file.clone(), 
+			source_id: self.source_id, 
+			line_index: Rc::new(OnceCell::new()),
 			whitespace: WhitespaceType::Ignore, 
 			indent: 0, 
 			indent_size: self.indent_size, 
-			in_indent: true 
+			in_indent: true,
+			recover: self.recover,
+			diagnostics: self.diagnostics.clone(),
+			comments: Rc::from(self.comments.as_slice()),
+			lossless: self.lossless,
+			tokens: Rc::from(self.tokens.as_slice()),
+			trivia_start: 0,
+			stopped: None,
+			closers: Vec::new()
 		}
 	}
 }
 
+/// A Checkpoint is a saved state of a [`CharStream`], that it can be rewound to with [`CharStream::rewind`].
+/// Creating and rewinding to a checkpoint are cheap, since the stream only borrows its source.
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+	offset: usize,
+	indent: u8,
+	in_indent: bool,
+	trivia_start: usize,
+	stopped: Option<(usize, Rc<ParseError>)>,
+	diagnostics: usize
+}
+
+/// A CharStream reads characters from a borrowed source, keeping track of the current position.
+/// Parsers that need to backtrack save a [`Checkpoint`] and rewind to it when they fail.
+/// Since the source is borrowed for `'src`, parsed values can hold slices of it instead of copies.
+/// ```
+/// # use parseal::parsing::charstream::CharStream;
+/// # fn main() {
+/// 	let mut buffer = CharStream::new("hello world").build();
+///
+/// 	let checkpoint = buffer.checkpoint();
+/// 	assert_eq!(buffer.next(), Some('h'));
+/// 	assert_eq!(buffer.next(), Some('e'));
+///
+/// 	buffer.rewind(checkpoint);
+/// 	assert_eq!(buffer.next(), Some('h'));
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
 #[derive(Debug, Clone)]
-pub struct CharStream {
-	chars: IntoIter<char>,
+pub struct CharStream<'src> {
+	source: &'src str,
+	offset: usize,
 	file: Option<String>,
-	file_id: u32,
-	column: usize,
-	row: usize,
-	index: usize,
-	eof: Position,
+	source_id: u32,
+	line_index: Rc<OnceCell<LineIndex>>,
 	whitespace: WhitespaceType,
 	indent: u8,
 	indent_size: u8,
-	in_indent: bool
+	in_indent: bool,
+	recover: bool,
+	diagnostics: Diagnostics,
+	comments: Rc<[Comment]>,
+	lossless: bool,
+	tokens: Rc<[String]>,
+	/// Where the next leading trivia starts: after the last character that was read as content, or after the last trailing trivia.
+	trivia_start: usize,
+	/// The error that ended the last repetition and the offset the repetition ended at, see [`CharStream::stop`].
+	stopped: Option<(usize, Rc<ParseError>)>,
+	closers: Vec<fn(&mut CharStream<'src>) -> bool>
 }
 
-impl CharStream {
-	pub fn new(value: String) -> CharStreamBuilder {
+impl<'src> CharStream<'src> {
+	#[allow(clippy::new_ret_no_self)]
+	pub fn new(value: &'src str) -> CharStreamBuilder<'src> {
 		CharStreamBuilder::new(value)
 	}
 
+	/// Returns the whole source that this stream reads from.
+	pub fn source(&self) -> &'src str {
+		self.source
+	}
+
+	/// Returns the current byte offset into the source.
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+
+	/// Returns the part of the source between byte offset `start` and the current offset.
+	pub fn slice_from(&self, start: usize) -> &'src str {
+		&self.source[start..self.offset]
+	}
+
+	/// Returns the name of the file the source was read from, if it was set with [`CharStreamBuilder::file`].
+	pub fn file(&self) -> Option<&str> {
+		self.file.as_deref()
+	}
+
+	pub fn source_id(&self) -> u32 {
+		self.source_id
+	}
+
 	pub fn position(&self) -> Position {
-		Position { column: self.column, row: self.row, index: self.index, file: self.file.clone(), file_id: self.file_id }
+		Position::new(self.offset, self.source_id)
+	}
+
+	/// Returns the line index of the source, which is built the first time it is needed and shared by all clones of the stream.
+	pub fn line_index(&self) -> &LineIndex {
+		self.line_index.get_or_init(|| LineIndex::new(self.source))
+	}
+
+	/// Resolves the line and column of `position`, with the column counted in characters.
+	pub fn line_col(&self, position: Position) -> LineCol {
+		self.line_index().line_col(self.source, position.offset)
+	}
+
+	/// Resolves the line and column of `position`, with the column counted in UTF-16 code units.
+	pub fn line_col_utf16(&self, position: Position) -> LineCol {
+		self.line_index().line_col_utf16(self.source, position.offset)
 	}
 
+	#[allow(clippy::should_implement_trait)]
 	pub fn next(&mut self) -> Option<char> {
-		let chr = match self.chars.next() {
-			Some('\n') => {
-				self.index += 1;
-				self.column = 0;
-				self.row += 1;
-				Some('\n')
+		self.skip_whitespace();
+		let chr = self.advance();
+		self.trivia_start = self.trivia_start.max(self.offset);
+
+		if let WhitespaceType::Indent = self.whitespace {
+			self.in_indent = false;
+		}
+
+		chr
+	}
+
+	/// Returns the character that [`CharStream::next`] would return, without consuming anything.
+	pub fn peek(&self) -> Option<char> {
+		match self.whitespace {
+			WhitespaceType::KeepAll => self.source[self.offset..].chars().next(),
+			_ => self.source[self.trivia_end(self.offset)..].chars().next()
+		}
+	}
+
+	/// Returns the offset of the first character after the whitespace and comments at `offset`.
+	fn trivia_end(&self, mut offset: usize) -> usize {
+		loop {
+			offset += self.source[offset..].len() - self.source[offset..].trim_start().len();
+			match self.comment_len(offset) {
+				Some(len) => offset += len,
+				None => return offset
 			}
-			Some(value) => {
-				self.index += 1;
-				self.column += 1;
-				Some(value)
+		}
+	}
+
+	/// Returns the length of the comment at `offset`, if there is one.
+	fn comment_len(&self, offset: usize) -> Option<usize> {
+		let text = &self.source[offset..];
+		self.comments.iter().find_map(|comment| comment.len(text)).filter(|len| *len > 0)
+	}
+
+	/// Skips the whitespace and comments that [`CharStream::next`] would skip under the current [`WhitespaceType`].
+	/// After this, [`CharStream::position`] is the position of the next character that will be returned.
+	pub fn skip_whitespace(&mut self) {
+		if let WhitespaceType::KeepAll = self.whitespace {
+			return;
+		}
+
+		loop {
+			while matches!(self.source[self.offset..].chars().next(), Some(chr) if chr.is_whitespace()) {
+				let chr = self.advance();
+				self.track_indent(chr);
 			}
-			None => None
-		};
 
-		match self.whitespace {
-			WhitespaceType::Ignore => {
-				match chr {
-					Some(c) if c.is_whitespace() => {
-						self.next()
+			match self.comment_len(self.offset) {
+				Some(len) => {
+					// a comment can span lines, so its characters are walked to keep the indentation of the line it ends on
+					let end = self.offset + len;
+					while self.offset < end {
+						let chr = self.advance();
+						self.track_indent(chr);
 					}
-					c => c
 				}
+				None => break
 			}
-			WhitespaceType::KeepAll => chr,
-			WhitespaceType::Indent => {
-				match chr {
-					Some(c) if c.is_whitespace() => {
-						match c {
-							'\t' => {
-								if self.in_indent {
-									self.indent += self.indent_size;
-								}
-							}
-							' ' => {
-								if self.in_indent {
-									self.indent += 1;
-								}
-							}
-							'\n' => {
-								self.in_indent = true;
-								self.indent = 0;
-							}
-							_ => {}
-						}
-						self.next()
-					}
-					c => {
-						self.in_indent = false;
-						c
-					}
+		}
+	}
+
+	/// Updates the indentation for a skipped character in [`WhitespaceType::Indent`] mode.
+	/// The text of a comment ends the indentation of its line, like any other text.
+	fn track_indent(&mut self, chr: Option<char>) {
+		if let WhitespaceType::Indent = self.whitespace {
+			match chr {
+				Some('\t') if self.in_indent => {
+					self.indent += self.indent_size;
+				}
+				Some(' ') if self.in_indent => {
+					self.indent += 1;
 				}
+				Some('\n') => {
+					self.in_indent = true;
+					self.indent = 0;
+				}
+				Some(chr) if !chr.is_whitespace() => {
+					self.in_indent = false;
+				}
+				_ => {}
 			}
 		}
 	}
 
+	/// Skips the trivia before a token, and returns the part of it that is the leading trivia of the token.
+	/// In a lossless stream this starts where the trailing trivia of the previous token ended, otherwise it is empty.
+	pub fn leading_trivia(&mut self) -> Span {
+		self.skip_whitespace();
+		let end = self.position();
+		if !self.lossless {
+			return Span::new(end, end);
+		}
+
+		let start = self.trivia_start.min(end.offset);
+		Span::new(Position::new(start, self.source_id), end)
+	}
+
+	/// Returns the trivia after a token that ends at the current position, up to the end of the line, without consuming it.
+	/// The next [`CharStream::leading_trivia`] starts after it.
+	/// It is empty when the stream is not lossless or keeps all whitespace, since the whitespace is content then.
+	pub fn trailing_trivia(&mut self) -> Span {
+		let start = self.position();
+		if !self.lossless || self.whitespace == WhitespaceType::KeepAll {
+			return Span::new(start, start);
+		}
+
+		let mut offset = self.offset;
+		loop {
+			let rest = &self.source[offset..];
+			offset += rest.len() - rest.trim_start_matches(|chr: char| chr.is_whitespace() && chr != '\n' && chr != '\r').len();
+			match self.comment_len(offset) {
+				Some(len) => offset += len,
+				None => break
+			}
+		}
+
+		self.trivia_start = offset;
+		Span::new(start, Position::new(offset, self.source_id))
+	}
+
+	pub fn is_lossless(&self) -> bool {
+		self.lossless
+	}
+
+	/// Returns the tokens that were registered with [`CharStreamBuilder::tokens`].
+	pub fn tokens(&self) -> &[String] {
+		&self.tokens
+	}
+
+	/// Splits the trivia in `span` into whitespace, line breaks and comments.
+	/// ```
+	/// # use parseal::parsing::charstream::{CharStream, Span, Position, TriviaPiece};
+	/// # fn main() {
+	/// 	let buffer = CharStream::new(" # note\n\t").line_comment("#").build();
+	/// 	let pieces = buffer.trivia_pieces(Span::new(Position::new(0, 0), Position::new(9, 0)));
+	/// 	assert_eq!(pieces, [TriviaPiece::Whitespace(" "), TriviaPiece::Comment("# note"), TriviaPiece::Newline("\n"), TriviaPiece::Whitespace("\t")]);
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn trivia_pieces(&self, span: Span) -> Vec<TriviaPiece<'src>> {
+		let mut pieces = Vec::new();
+		let mut offset = span.start.offset;
+		let end = span.end.offset.min(self.source.len());
+		while offset < end {
+			let rest = &self.source[offset..end];
+			let len = if let Some(len) = self.comment_len(offset).map(|len| len.min(rest.len())) {
+				pieces.push(TriviaPiece::Comment(&rest[..len]));
+				len
+			} else if rest.starts_with('\n') || rest.starts_with("\r\n") {
+				let len = rest.find('\n').map_or(1, |newline| newline + 1);
+				pieces.push(TriviaPiece::Newline(&rest[..len]));
+				len
+			} else {
+				let len = rest.char_indices()
+					.find(|(index, chr)| *index > 0 && (*chr == '\n' || rest[*index..].starts_with("\r\n") || self.comment_len(offset + index).is_some()))
+					.map_or(rest.len(), |(index, _)| index);
+				pieces.push(TriviaPiece::Whitespace(&rest[..len]));
+				len
+			};
+			offset += len;
+		}
+		pieces
+	}
+
+	fn advance(&mut self) -> Option<char> {
+		let chr = self.source[self.offset..].chars().next()?;
+		self.offset += chr.len_utf8();
+		Some(chr)
+	}
+
+	pub fn checkpoint(&self) -> Checkpoint {
+		Checkpoint {
+			offset: self.offset,
+			indent: self.indent,
+			in_indent: self.in_indent,
+			trivia_start: self.trivia_start,
+			stopped: self.stopped.clone(),
+			diagnostics: self.diagnostics.len()
+		}
+	}
+
+	/// Returns the stream to the state it was in when `checkpoint` was created.
+	/// This works in both directions, and drops the diagnostics that were reported after a checkpoint that lies in the past.
+	pub fn rewind(&mut self, checkpoint: Checkpoint) {
+		self.offset = checkpoint.offset;
+		self.indent = checkpoint.indent;
+		self.in_indent = checkpoint.in_indent;
+		self.trivia_start = checkpoint.trivia_start;
+		self.stopped = checkpoint.stopped;
+		self.diagnostics.truncate(checkpoint.diagnostics);
+	}
+
+	/// Runs `parse`, and rewinds the stream to where it was before if it fails.
+	/// ```
+	/// # use parseal::parsing::{charstream::CharStream, tokens, Parse};
+	/// # fn main() {
+	/// 	let mut buffer = CharStream::new(", ;").build();
+	///
+	/// 	let value = buffer.attempt(|value| <(tokens::Comma, tokens::Comma)>::parse(value));
+	/// 	assert!(value.is_err());
+	/// 	assert!(tokens::Comma::parse(&mut buffer).is_ok());
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn attempt<T>(&mut self, parse: impl FnOnce(&mut CharStream<'src>) -> Result<T, ParseError>) -> Result<T, ParseError> {
+		let checkpoint = self.checkpoint();
+		let result = parse(self);
+		if result.is_err() {
+			self.rewind(checkpoint);
+		}
+		result
+	}
+
+	/// Records the error that ended a repetition, like the item of a [`List`](super::List) that failed to parse.
+	/// The repetition itself succeeds, so the error would be lost, but it often points further into the input
+	/// than the error of what comes after the repetition. [`CharStream::after_stop`] merges the two.
+	/// The error is kept for the current offset only, once the stream moves on it no longer belongs to what comes next.
+	/// ```
+	/// # use parseal::parsing::{charstream::CharStream, tokens, Parse, Number};
+	/// # fn main() {
+	/// 	// the second pair stops the repetition at `x`, after the `3` that it read.
+	/// 	let mut buffer = CharStream::new("1 2 3 x").build();
+	/// 	assert_eq!(Vec::<(Number, Number)>::parse(&mut buffer).unwrap().len(), 1);
+	///
+	/// 	// right after the repetition, its error got further than the `,` would.
+	/// 	let mut stopped = buffer.clone();
+	/// 	let error = stopped.after_stop(tokens::Comma::parse).unwrap_err();
+	/// 	assert_eq!(error.message(), "expected number, found `x`");
+	///
+	/// 	// the `3` is read by another parser, so the error does not belong to the `,` after it.
+	/// 	Number::parse(&mut buffer).unwrap();
+	/// 	let error = buffer.after_stop(tokens::Comma::parse).unwrap_err();
+	/// 	assert_eq!(error.message(), "expected `,`, found `x`");
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn stop(&mut self, error: ParseError) {
+		self.stopped = Some((self.offset, Rc::new(error)));
+	}
+
+	/// Runs `parse` for what comes after a repetition, and merges its error with the error that ended the repetition,
+	/// so the error that got furthest into the input is kept, or the expected-sets are combined if both failed at the same position.
+	/// The recorded error is only merged into the first parser that runs after it, and only if that parser starts where the repetition ended.
+	pub fn after_stop<T>(&mut self, parse: impl FnOnce(&mut CharStream<'src>) -> Result<T, ParseError>) -> Result<T, ParseError> {
+		let stopped = self.stopped.take().filter(|(offset, _)| *offset == self.offset);
+		parse(self).map_err(|error| match stopped {
+			Some((_, stopped)) => Rc::unwrap_or_clone(stopped).merge(error),
+			None => error
+		})
+	}
+
+	/// Checks whether `parse` succeeds at the current position, without consuming anything.
+	pub fn lookahead<T>(&mut self, parse: impl FnOnce(&mut CharStream<'src>) -> Result<T, ParseError>) -> bool {
+		let checkpoint = self.checkpoint();
+		let found = parse(self).is_ok();
+		self.rewind(checkpoint);
+		found
+	}
+
+	/// Moves the stream to `position`, which can be before or after the current position.
+	/// Going back from a [`Position`] has to read the source from the start, [`CharStream::rewind`] is the cheap way to backtrack.
 	pub fn goto(&mut self, position: Position) -> Result<(), ParseError> {
-		if self.file_id != position.file_id {
-			return Err(ParseError("Could not go to position in different buffer.".to_string(), position));
+		if self.source_id != position.source_id {
+			return Err(ParseError::new("Could not go to position in different buffer.", position));
 		}
 
-		if position < self.position() {
-			return Err(ParseError("Charstream does not support going back.".to_string(), position));
+		if position.offset > self.source.len() {
+			return Err(ParseError::new("Charstream can not go to position after end of buffer.", Position::new(self.source.len(), self.source_id)));
 		}
 
-		if position > self.eof {
-			return Err(ParseError("Charstream can not go to position after end of buffer.".to_string(), self.eof.clone()));
+		if position < self.position() {
+			self.rewind(Checkpoint { offset: 0, indent: 0, in_indent: true, trivia_start: 0, stopped: None, diagnostics: self.diagnostics.len() });
 		}
 
 		while self.position() < position {
+			self.skip_whitespace();
+			if self.position() >= position {
+				break;
+			}
+
 			self.next();
 		}
 
 		Ok(())
 	}
 
+	pub fn whitespace(&self) -> WhitespaceType {
+		self.whitespace
+	}
+
 	pub fn set_whitespace(&mut self, whitespace: WhitespaceType) {
 		self.whitespace = whitespace;
 	}
@@ -236,4 +821,45 @@ impl CharStream {
 	pub fn indent(&self) -> u8 {
 		self.indent
 	}
-}
\ No newline at end of file
+
+	pub fn is_recovering(&self) -> bool {
+		self.recover
+	}
+
+	/// Returns the sink for warnings and recovered errors.
+	/// All clones of a stream share the same diagnostics.
+	pub fn diagnostics(&self) -> &Diagnostics {
+		&self.diagnostics
+	}
+
+	/// Registers a check for the closing side of the delimiter that is currently being parsed.
+	/// Recovering parsers stop skipping input when this check succeeds.
+	pub(crate) fn push_closer(&mut self, closer: fn(&mut CharStream<'src>) -> bool) {
+		self.closers.push(closer);
+	}
+
+	pub(crate) fn pop_closer(&mut self) {
+		self.closers.pop();
+	}
+
+	pub(crate) fn in_delimiter(&self) -> bool {
+		!self.closers.is_empty()
+	}
+
+	/// Checks whether the closing side of the innermost delimiter is next in the stream, without consuming it.
+	pub(crate) fn at_closer(&mut self) -> bool {
+		let closer = match self.closers.last() {
+			Some(closer) => *closer,
+			None => return false
+		};
+
+		let checkpoint = self.checkpoint();
+		let found = closer(self);
+		self.rewind(checkpoint);
+		found
+	}
+
+	pub(crate) fn at_end(&self) -> bool {
+		self.peek().is_none()
+	}
+}
@@ -0,0 +1,337 @@
+use std::{fmt::{self, Write}, rc::Rc, cell::RefCell};
+
+use super::{ErrorKind, ParseError, charstream::{LineIndex, Span}, source::SourceMap};
+
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// The number of columns a tab takes up in a rendered snippet.
+const TAB_WIDTH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+	Error,
+	Warning,
+	Note
+}
+
+impl fmt::Display for Severity {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Error => write!(f, "error"),
+			Self::Warning => write!(f, "warning"),
+			Self::Note => write!(f, "note")
+		}
+	}
+}
+
+/// A Diagnostic is a finding about the input that is reported while parsing continues,
+/// like an error that was recovered from or a warning about a deprecated construct.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+	severity: Severity,
+	message: String,
+	span: Span,
+	error: Option<ParseError>
+}
+
+impl Diagnostic {
+	pub fn new(severity: Severity, message: &str, span: Span) -> Self {
+		Self { severity, message: message.to_owned(), span, error: None }
+	}
+
+	pub fn warning(message: &str, span: Span) -> Self {
+		Self::new(Severity::Warning, message, span)
+	}
+
+	pub fn note(message: &str, span: Span) -> Self {
+		Self::new(Severity::Note, message, span)
+	}
+
+	pub fn severity(&self) -> Severity {
+		self.severity
+	}
+
+	pub fn message(&self) -> &str {
+		&self.message
+	}
+
+	pub fn span(&self) -> &Span {
+		&self.span
+	}
+
+	/// Returns the error this diagnostic was created from, if it was created from a [`ParseError`].
+	pub fn error(&self) -> Option<&ParseError> {
+		self.error.as_ref()
+	}
+}
+
+impl From<ParseError> for Diagnostic {
+	fn from(error: ParseError) -> Self {
+		Self { severity: Severity::Error, message: error.message(), span: *error.span(), error: Some(error) }
+	}
+}
+
+impl fmt::Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}: {} at {}", self.severity, self.message, self.span.start)
+	}
+}
+
+/// Diagnostics is the sink that parsers push their [`Diagnostic`]s into while parsing continues.
+/// Every [`super::charstream::CharStream`] has one, and all clones of a stream share it.
+/// ```
+/// # use parseal::parsing::{charstream::{CharStream, Span}, diagnostic::Severity, Identifier, Parse, ParseError};
+/// #[derive(Clone)]
+/// struct Bool<'src>(Identifier<'src>);
+///
+/// impl<'src> Parse<'src> for Bool<'src> {
+/// 	fn parse(value: &mut CharStream<'src>) -> Result<Self, ParseError> {
+/// 		let identifier = Identifier::parse(value)?;
+/// 		if identifier == "yes" || identifier == "no" {
+/// 			value.diagnostics().warn("`yes` and `no` are deprecated, use `true` or `false`", identifier.span());
+/// 		}
+/// 		Ok(Self(identifier))
+/// 	}
+///
+/// 	fn span(&self) -> Span {
+/// 		self.0.span()
+/// 	}
+/// }
+///
+/// # fn main() {
+/// 	let mut buffer = CharStream::new("yes").build();
+/// 	assert!(Bool::parse(&mut buffer).is_ok());
+///
+/// 	let diagnostics = buffer.diagnostics().to_vec();
+/// 	assert_eq!(diagnostics.len(), 1);
+/// 	assert_eq!(diagnostics[0].severity(), Severity::Warning);
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+	diagnostics: Rc<RefCell<Vec<Diagnostic>>>
+}
+
+impl Diagnostics {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn push(&self, diagnostic: Diagnostic) {
+		self.diagnostics.borrow_mut().push(diagnostic);
+	}
+
+	/// Records an error that was recovered from.
+	pub fn error(&self, error: ParseError) {
+		self.push(Diagnostic::from(error));
+	}
+
+	pub fn warn(&self, message: &str, span: Span) {
+		self.push(Diagnostic::warning(message, span));
+	}
+
+	pub fn note(&self, message: &str, span: Span) {
+		self.push(Diagnostic::note(message, span));
+	}
+
+	/// Drops the diagnostics that were reported after the first `len`, used when a parser backtracks.
+	pub(crate) fn truncate(&self, len: usize) {
+		self.diagnostics.borrow_mut().truncate(len);
+	}
+
+	pub fn len(&self) -> usize {
+		self.diagnostics.borrow().len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.diagnostics.borrow().is_empty()
+	}
+
+	pub fn has_errors(&self) -> bool {
+		self.diagnostics.borrow().iter().any(|diagnostic| diagnostic.severity == Severity::Error)
+	}
+
+	/// Returns all diagnostics, in the order they were reported.
+	pub fn to_vec(&self) -> Vec<Diagnostic> {
+		self.diagnostics.borrow().clone()
+	}
+
+	/// Returns the errors that were recovered from, in the order they were reported.
+	pub fn errors(&self) -> Vec<ParseError> {
+		self.diagnostics.borrow().iter().filter_map(|diagnostic| diagnostic.error.clone()).collect()
+	}
+}
+
+/// A Renderer turns a [`ParseError`] into an annotated snippet of the source it was parsed from.
+/// The snippet shows the name of the file, the offending lines with their line numbers, and carets under the span of the error.
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, diagnostic::Renderer, tokens, Parse, Number, List, Group};
+/// # fn main() {
+/// 	let source = "[0, 1\n 2]";
+/// 	let mut buffer = CharStream::new(source).build();
+///
+/// 	let error = Group::<tokens::Bracket, List<Number, tokens::Comma>>::parse(&mut buffer).unwrap_err();
+/// 	let rendered = Renderer::new().render(&error, source);
+///
+/// 	assert_eq!(rendered, "\
+/// error: expected one of `,`, `]`, found `2`
+///  --> 2:2
+///   |
+/// 2 |  2]
+///   |  ^ unexpected `2`
+/// ");
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+#[derive(Debug, Clone, Default)]
+pub struct Renderer {
+	color: bool,
+	file: Option<String>
+}
+
+impl Renderer {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets whether the rendered output uses ANSI colour codes.
+	pub fn color(&mut self, color: bool) -> &mut Self {
+		self.color = color;
+		self
+	}
+
+	/// Sets the name of the file that is shown in the location line of the snippet.
+	pub fn file(&mut self, file: &str) -> &mut Self {
+		self.file = Some(file.to_owned());
+		self
+	}
+
+	pub fn render(&self, error: &ParseError, source: &str) -> String {
+		self.render_diagnostic(&Diagnostic::from(error.clone()), source)
+	}
+
+	pub fn render_diagnostic(&self, diagnostic: &Diagnostic, source: &str) -> String {
+		self.render_snippet(diagnostic, source, &LineIndex::new(source), self.file.as_deref())
+	}
+
+	/// Renders `error` against the source it came from in `sources`, using the name that source was registered with.
+	/// ```
+	/// # use parseal::parsing::{source::SourceMap, diagnostic::Renderer, tokens, Parse, Identifier};
+	/// # fn main() {
+	/// 	let mut sources = SourceMap::new();
+	/// 	let id = sources.add("config.conf", "name;".to_owned());
+	///
+	/// 	let mut buffer = sources.stream(id).unwrap().build();
+	/// 	let error = <(Identifier, tokens::Colon)>::parse(&mut buffer).unwrap_err();
+	///
+	/// 	assert!(Renderer::new().render_in(&error, &sources).contains(" --> config.conf:1:5"));
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn render_in(&self, error: &ParseError, sources: &SourceMap) -> String {
+		self.render_diagnostic_in(&Diagnostic::from(error.clone()), sources)
+	}
+
+	pub fn render_diagnostic_in(&self, diagnostic: &Diagnostic, sources: &SourceMap) -> String {
+		match sources.file(diagnostic.span().start) {
+			Some(file) => self.render_snippet(diagnostic, file.text(), file.lines(), Some(file.name())),
+			None => self.render_diagnostic(diagnostic, "")
+		}
+	}
+
+	fn render_snippet(&self, diagnostic: &Diagnostic, source: &str, index: &LineIndex, file: Option<&str>) -> String {
+		let span = diagnostic.span();
+		let start_offset = span.start.offset.min(source.len());
+		let start = index.line_col(source, start_offset);
+		let end = index.line_col(source, span.end.offset.min(source.len()).max(start_offset));
+
+		let first = start.line;
+		let last = end.line.max(first);
+		let gutter = (last + 1).to_string().len();
+
+		let mut output = String::new();
+		let color = match diagnostic.severity() {
+			Severity::Error => RED,
+			Severity::Warning => YELLOW,
+			Severity::Note => BLUE
+		};
+		let _ = writeln!(output, "{}{}{}{}: {}{}", self.paint(color), diagnostic.severity(), self.paint(RESET), self.paint(BOLD), diagnostic.message(), self.paint(RESET));
+
+		let location = match file {
+			Some(file) => format!("{}:{}", file, start),
+			None => start.to_string()
+		};
+		let _ = writeln!(output, "{:gutter$}{}-->{} {}", "", self.paint(BLUE), self.paint(RESET), location);
+		let _ = writeln!(output, "{:gutter$} {}|{}", "", self.paint(BLUE), self.paint(RESET));
+
+		for row in first..=last {
+			let line = &source[index.line_range(source, row)];
+			let line = line.strip_suffix('\r').unwrap_or(line);
+			let width = line.chars().count();
+
+			let from = if row == first { start.column.min(width) } else { 0 };
+			let to = if row == end.line { end.column.min(width) } else { width };
+			let to = if row == last && to <= from { from + 1 } else { to };
+
+			let _ = writeln!(output, "{}{:>gutter$} |{} {}", self.paint(BLUE), row + 1, self.paint(RESET), expand_tabs(line));
+
+			let offset = display_width(line.chars().take(from));
+			let carets = display_width(line.chars().skip(from).take(to - from)).max(to - from);
+			if carets == 0 {
+				continue;
+			}
+
+			let label = match (row == last, diagnostic.error().and_then(|error| self.label(error))) {
+				(true, Some(label)) => format!(" {}", label),
+				_ => String::new()
+			};
+			let _ = writeln!(
+				output,
+				"{:gutter$} {}|{} {:offset$}{}{}{}{}",
+				"",
+				self.paint(BLUE),
+				self.paint(RESET),
+				"",
+				self.paint(color),
+				"^".repeat(carets),
+				label,
+				self.paint(RESET)
+			);
+		}
+
+		if let Some(error) = diagnostic.error().filter(|error| !error.context().is_empty()) {
+			let _ = writeln!(output, "{:gutter$} {}={} note: while parsing {}", "", self.paint(BLUE), self.paint(RESET), error.breadcrumbs());
+		}
+
+		output
+	}
+
+	fn label(&self, error: &ParseError) -> Option<String> {
+		match (error.kind(), error.found()) {
+			(ErrorKind::UnexpectedEof, _) => Some(String::from("unexpected end of input")),
+			(ErrorKind::Custom(_), _) | (_, None) => None,
+			(_, Some(found)) => Some(format!("unexpected `{}`", found))
+		}
+	}
+
+	fn paint(&self, code: &'static str) -> &'static str {
+		if self.color {
+			code
+		} else {
+			""
+		}
+	}
+}
+
+fn expand_tabs(line: &str) -> String {
+	line.replace('\t', &" ".repeat(TAB_WIDTH))
+}
+
+fn display_width(chars: impl Iterator<Item = char>) -> usize {
+	chars.map(|chr| if chr == '\t' { TAB_WIDTH } else { 1 }).sum()
+}
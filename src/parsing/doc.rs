@@ -0,0 +1,160 @@
+/// A Doc describes a layout of text that can be rendered at different line widths, as in Wadler's "A prettier printer".
+/// The line breaks in a [`Doc::group`] are all rendered flat if the group fits on the line, otherwise they all break.
+/// ```
+/// # use parseal::parsing::doc::Doc;
+/// # fn main() {
+/// 	let items = Doc::concat(vec![
+/// 		Doc::text("1,"), Doc::line(),
+/// 		Doc::text("2,"), Doc::line(),
+/// 		Doc::text("3")
+/// 	]);
+/// 	let doc = Doc::group(Doc::concat(vec![
+/// 		Doc::text("["),
+/// 		Doc::nest(2, Doc::concat(vec![Doc::softline(), items])),
+/// 		Doc::softline(),
+/// 		Doc::text("]")
+/// 	]));
+///
+/// 	assert_eq!(doc.render(80), "[1, 2, 3]");
+/// 	assert_eq!(doc.render(8), "[\n  1,\n  2,\n  3\n]");
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Doc {
+	Nil,
+	Text(String),
+	/// A line break, that is rendered as the text in it when the group it is in is flat.
+	Line(&'static str),
+	/// A line break that is always rendered, the groups around it can never be flat.
+	HardLine,
+	Concat(Vec<Doc>),
+	/// Indents the lines that break inside the doc by a number of spaces.
+	Nest(usize, Box<Doc>),
+	Group(Box<Doc>)
+}
+
+/// Whether the line breaks of a group are rendered flat or broken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+	Flat,
+	Break
+}
+
+impl Doc {
+	pub fn text(text: &str) -> Self {
+		Self::Text(text.to_owned())
+	}
+
+	/// A line break that is a space when it is flat.
+	pub fn line() -> Self {
+		Self::Line(" ")
+	}
+
+	/// A line break that is nothing when it is flat.
+	pub fn softline() -> Self {
+		Self::Line("")
+	}
+
+	pub fn hardline() -> Self {
+		Self::HardLine
+	}
+
+	pub fn concat(docs: Vec<Doc>) -> Self {
+		Self::Concat(docs)
+	}
+
+	pub fn nest(indent: usize, doc: Doc) -> Self {
+		Self::Nest(indent, Box::new(doc))
+	}
+
+	pub fn group(doc: Doc) -> Self {
+		Self::Group(Box::new(doc))
+	}
+
+	/// Renders the doc, breaking the groups that do not fit within `width` columns.
+	/// Lines are not indented until text is written on them, so empty lines have no trailing whitespace.
+	pub fn render(&self, width: usize) -> String {
+		let mut output = String::new();
+		let mut column = 0;
+		let mut indent_pending = None;
+		let mut stack = vec![(0, Mode::Break, self)];
+
+		while let Some((indent, mode, doc)) = stack.pop() {
+			let text = match doc {
+				Self::Nil => continue,
+				Self::Text(text) => text.as_str(),
+				Self::Line(flat) if mode == Mode::Flat => flat,
+				Self::Line(_) | Self::HardLine => {
+					output.push('\n');
+					indent_pending = Some(indent);
+					column = indent;
+					continue;
+				}
+				Self::Concat(docs) => {
+					stack.extend(docs.iter().rev().map(|doc| (indent, mode, doc)));
+					continue;
+				}
+				Self::Nest(nested, doc) => {
+					stack.push((indent + nested, mode, doc));
+					continue;
+				}
+				Self::Group(doc) => {
+					let mode = match mode {
+						Mode::Break if !fits(width as isize - column as isize, (indent, Mode::Flat, doc), &stack) => Mode::Break,
+						_ => Mode::Flat
+					};
+					stack.push((indent, mode, doc));
+					continue;
+				}
+			};
+
+			if text.is_empty() {
+				continue;
+			}
+			if let Some(indent) = indent_pending.take() {
+				output.push_str(&" ".repeat(indent));
+			}
+			output.push_str(text);
+			column = match text.rfind('\n') {
+				Some(newline) => text[newline + 1..].chars().count(),
+				None => column + text.chars().count()
+			};
+		}
+
+		output
+	}
+}
+
+/// Checks whether `first` fits in the `remaining` columns when it is flat,
+/// together with what comes after it in `rest` up to the next line break.
+fn fits(mut remaining: isize, first: (usize, Mode, &Doc), rest: &[(usize, Mode, &Doc)]) -> bool {
+	let mut stack = vec![first];
+	let mut rest = rest.iter().rev();
+
+	while remaining >= 0 {
+		let (indent, mode, doc) = match stack.pop() {
+			Some(item) => item,
+			None => match rest.next() {
+				Some(item) => *item,
+				None => return true
+			}
+		};
+
+		match doc {
+			Doc::Nil => {}
+			Doc::Text(text) => match text.split_once('\n') {
+				Some((line, _)) => return remaining >= line.chars().count() as isize,
+				None => remaining -= text.chars().count() as isize
+			}
+			Doc::Line(flat) if mode == Mode::Flat => remaining -= flat.chars().count() as isize,
+			Doc::Line(_) => return true,
+			Doc::HardLine => return mode == Mode::Break,
+			Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (indent, mode, doc))),
+			Doc::Nest(nested, doc) => stack.push((indent + nested, mode, doc)),
+			Doc::Group(doc) => stack.push((indent, mode, doc))
+		}
+	}
+
+	false
+}
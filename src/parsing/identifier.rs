@@ -0,0 +1,106 @@
+/// IdentifierRules decide which characters a [`super::Ident`] can start with and continue with.
+/// By default these are the Unicode `XID_Start` and `XID_Continue` characters, plus the extra characters of the rules.
+/// The extra start characters can also be used to continue an identifier.
+/// ```
+/// # use parseal::parsing::{identifier::IdentifierRules, charstream::CharStream, Ident, Parse};
+/// #[derive(Clone)]
+/// struct ShellVariable;
+///
+/// impl IdentifierRules for ShellVariable {
+/// 	const EXTRA_START: &'static [char] = &['$', '_'];
+/// }
+///
+/// # fn main() {
+/// 	let mut buffer = CharStream::new("$HOME_DIR").build();
+/// 	let value = Ident::<ShellVariable>::parse(&mut buffer).unwrap();
+/// 	assert_eq!(value.as_str(), "$HOME_DIR");
+/// # }
+/// ```
+/// The reserved words of the rules are rejected, but words that only start with one are not.
+/// ```
+/// # use parseal::parsing::{identifier::IdentifierRules, charstream::CharStream, Ident, Parse};
+/// #[derive(Clone)]
+/// struct Name;
+///
+/// impl IdentifierRules for Name {
+/// 	const RESERVED: &'static [&'static str] = &["true", "false"];
+/// }
+///
+/// # fn main() {
+/// 	let error = Ident::<Name>::parse(&mut CharStream::new("true").build()).unwrap_err();
+/// 	assert_eq!(error.message(), "expected identifier, found `true`");
+/// 	assert!(Ident::<Name>::parse(&mut CharStream::new("trueish").build()).is_ok());
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+pub trait IdentifierRules: Clone {
+	/// Characters an identifier can start with, on top of the ones [`IdentifierRules::is_start`] accepts.
+	const EXTRA_START: &'static [char] = &[];
+	/// Characters an identifier can continue with, on top of the ones [`IdentifierRules::is_continue`] accepts.
+	const EXTRA_CONTINUE: &'static [char] = &[];
+	/// Reserved words, like the keywords of a language, that are not accepted as an identifier.
+	const RESERVED: &'static [&'static str] = &[];
+
+	fn is_start(chr: char) -> bool {
+		unicode_ident::is_xid_start(chr)
+	}
+
+	fn is_continue(chr: char) -> bool {
+		unicode_ident::is_xid_continue(chr)
+	}
+}
+
+/// Whether `chr` can start an identifier under the rules `R`, including the extra characters.
+pub(crate) fn starts<R: IdentifierRules>(chr: char) -> bool {
+	R::is_start(chr) || R::EXTRA_START.contains(&chr)
+}
+
+/// Whether `chr` can continue an identifier under the rules `R`, including the extra characters.
+pub(crate) fn continues<R: IdentifierRules>(chr: char) -> bool {
+	R::is_continue(chr) || R::EXTRA_CONTINUE.contains(&chr) || R::EXTRA_START.contains(&chr)
+}
+
+/// The rules of [`super::Identifier`]: a letter, followed by letters and digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alphanumeric;
+
+impl IdentifierRules for Alphanumeric {
+	fn is_start(chr: char) -> bool {
+		chr.is_alphabetic()
+	}
+
+	fn is_continue(chr: char) -> bool {
+		chr.is_alphanumeric()
+	}
+}
+
+/// Unicode identifiers, as described in Unicode Standard Annex #31.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Xid;
+
+impl IdentifierRules for Xid {}
+
+/// The identifiers of Rust, like `snake_case` and `_private`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rust;
+
+impl IdentifierRules for Rust {
+	const EXTRA_START: &'static [char] = &['_'];
+}
+
+/// The identifiers of CSS, like `kebab-case`, `-webkit-box` and `--custom-property`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Css;
+
+impl IdentifierRules for Css {
+	const EXTRA_START: &'static [char] = &['-', '_'];
+}
+
+/// The attribute names of HTML, like `data-id`, `aria-label` and `xml:lang`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HtmlAttribute;
+
+impl IdentifierRules for HtmlAttribute {
+	const EXTRA_START: &'static [char] = &['_', ':'];
+	const EXTRA_CONTINUE: &'static [char] = &['-', '.'];
+}
@@ -0,0 +1,188 @@
+/// A NumberDialect decides which numeric literals a [`super::NumberLiteral`] accepts.
+/// The defaults of the constants are the ones of [`Lenient`], which accepts most of what the other dialects accept.
+pub trait NumberDialect: Clone {
+	/// The signs that a number can start with.
+	const SIGNS: &'static [char] = &['-', '+'];
+	/// Whether the integer part can have more than one digit when it starts with `0`.
+	const LEADING_ZEROS: bool = true;
+	/// Whether a number can start with `.`, like `.5`.
+	const LEADING_DOT: bool = true;
+	const FRACTION: bool = true;
+	const EXPONENT: bool = true;
+	/// Whether integers can have a `0x`, `0o` or `0b` prefix.
+	const RADIX_PREFIXES: bool = true;
+	/// Whether a number with a radix prefix can have a sign.
+	const SIGNED_RADIX: bool = true;
+	/// The character that can be used to group digits, like the `_` in `1_000`.
+	const SEPARATOR: Option<char> = Some('_');
+	/// Whether the separator has to be between two digits, instead of anywhere after the first digit.
+	const SEPARATOR_BETWEEN_DIGITS: bool = false;
+	/// Words that are numbers on their own, like `inf` and `nan` in [`Toml`].
+	const SPECIAL: &'static [&'static str] = &[];
+	/// Type suffixes, like the `u8` in `255u8`. A suffix that starts with `f` makes the number a float.
+	const SUFFIXES: &'static [&'static str] = &[];
+}
+
+/// Accepts every number that one of the other dialects accepts, except type suffixes and the words `inf` and `nan`,
+/// which would otherwise be numbers wherever an identifier can be too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lenient;
+
+impl NumberDialect for Lenient {}
+
+/// The numbers of JSON: `-12`, `0.5` and `1e-10`, without leading zeros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Json;
+
+impl NumberDialect for Json {
+	const SIGNS: &'static [char] = &['-'];
+	const LEADING_ZEROS: bool = false;
+	const LEADING_DOT: bool = false;
+	const RADIX_PREFIXES: bool = false;
+	const SEPARATOR: Option<char> = None;
+}
+
+/// The numbers of TOML: `+1_000`, `0xDEAD_BEEF`, `6.626e-34`, `inf` and `nan`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Toml;
+
+impl NumberDialect for Toml {
+	const LEADING_ZEROS: bool = false;
+	const LEADING_DOT: bool = false;
+	const SIGNED_RADIX: bool = false;
+	const SEPARATOR_BETWEEN_DIGITS: bool = true;
+	const SPECIAL: &'static [&'static str] = &["inf", "nan"];
+}
+
+/// The numeric literals of Rust: `1_000u32`, `0b1010`, `2.5e3f64`.
+/// A `-` is an operator in Rust, not part of the literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rust;
+
+impl NumberDialect for Rust {
+	const SIGNS: &'static [char] = &[];
+	const LEADING_DOT: bool = false;
+	const SUFFIXES: &'static [&'static str] = &[
+		"u8", "u16", "u32", "u64", "u128", "usize",
+		"i8", "i16", "i32", "i64", "i128", "isize",
+		"f32", "f64"
+	];
+}
+
+/// Whether a number is an integer or has a fraction or exponent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberKind {
+	Integer,
+	Float
+}
+
+/// The result of scanning a number: its length in bytes, its kind, its radix and the length of its suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Scanned {
+	pub(crate) len: usize,
+	pub(crate) kind: NumberKind,
+	pub(crate) radix: u32,
+	pub(crate) suffix: usize
+}
+
+/// Finds the longest number at the start of `text` that the dialect `D` accepts.
+pub(crate) fn scan<D: NumberDialect>(text: &str) -> Option<Scanned> {
+	let bytes = text.as_bytes();
+	let signed = text.starts_with(D::SIGNS);
+	let start = if signed { 1 } else { 0 };
+
+	if let Some(special) = D::SPECIAL.iter().find(|special| text[start..].starts_with(*special)) {
+		let len = start + special.len();
+		if !continues_word(text, len) {
+			return Some(Scanned { len, kind: NumberKind::Float, radix: 10, suffix: 0 });
+		}
+	}
+
+	if D::RADIX_PREFIXES && (!signed || D::SIGNED_RADIX) && bytes.get(start) == Some(&b'0') {
+		let radix = match bytes.get(start + 1) {
+			Some(b'x') => 16,
+			Some(b'o') => 8,
+			Some(b'b') => 2,
+			_ => 0
+		};
+
+		let end = if radix == 0 { start } else { digits::<D>(bytes, start + 2, radix) };
+		if end > start + 2 {
+			return Some(with_suffix::<D>(text, end, NumberKind::Integer, radix));
+		}
+	}
+
+	let mut end = match bytes.get(start) {
+		Some(b'0') if !D::LEADING_ZEROS => start + 1,
+		_ => digits::<D>(bytes, start, 10)
+	};
+	let mut kind = NumberKind::Integer;
+
+	let leading_dot = end == start && D::LEADING_DOT;
+	if end == start && !leading_dot {
+		return None;
+	}
+
+	if (D::FRACTION || leading_dot) && bytes.get(end) == Some(&b'.') && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) {
+		end = digits::<D>(bytes, end + 1, 10);
+		kind = NumberKind::Float;
+	} else if leading_dot {
+		return None;
+	}
+
+	if D::EXPONENT && matches!(bytes.get(end), Some(b'e' | b'E')) {
+		let exponent = match bytes.get(end + 1) {
+			Some(b'+' | b'-') => end + 2,
+			_ => end + 1
+		};
+
+		if bytes.get(exponent).is_some_and(u8::is_ascii_digit) {
+			end = digits::<D>(bytes, exponent, 10);
+			kind = NumberKind::Float;
+		}
+	}
+
+	Some(with_suffix::<D>(text, end, kind, 10))
+}
+
+/// Returns the end of the digits in `radix` that start at `start`, including separators.
+/// It returns `start` if there is no digit there.
+fn digits<D: NumberDialect>(bytes: &[u8], start: usize, radix: u32) -> usize {
+	let is_digit = |byte: Option<&u8>| byte.is_some_and(|byte| (*byte as char).is_digit(radix));
+	if !is_digit(bytes.get(start)) {
+		return start;
+	}
+
+	let mut end = start + 1;
+	loop {
+		match bytes.get(end) {
+			byte if is_digit(byte) => end += 1,
+			Some(byte) if Some(*byte as char) == D::SEPARATOR => {
+				if D::SEPARATOR_BETWEEN_DIGITS && !is_digit(bytes.get(end + 1)) {
+					break;
+				}
+				end += 1;
+			}
+			_ => break
+		}
+	}
+	end
+}
+
+fn with_suffix<D: NumberDialect>(text: &str, end: usize, kind: NumberKind, radix: u32) -> Scanned {
+	let suffix = D::SUFFIXES.iter()
+		.filter(|suffix| radix == 10 || !suffix.starts_with('f'))
+		.find(|suffix| text[end..].starts_with(*suffix) && !continues_word(text, end + suffix.len()));
+
+	match suffix {
+		Some(suffix) => {
+			let kind = if suffix.starts_with('f') { NumberKind::Float } else { kind };
+			Scanned { len: end + suffix.len(), kind, radix, suffix: suffix.len() }
+		}
+		None => Scanned { len: end, kind, radix, suffix: 0 }
+	}
+}
+
+fn continues_word(text: &str, offset: usize) -> bool {
+	text[offset..].chars().next().is_some_and(|chr| chr.is_alphanumeric() || chr == '_')
+}
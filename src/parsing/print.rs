@@ -0,0 +1,590 @@
+use super::{doc::Doc, charstream::Trivia, tokens::{Delimiter, Token}, number::NumberDialect, identifier::IdentifierRules, Group, List, Indent, QuotedString, RawString, TripleQuotedString, BlockScalar, NumberLiteral, Ident, ErrorNode};
+
+/// Print turns a parsed value back into text, it is the counterpart of [`Parse`](super::Parse).
+/// The text is laid out by a [`Printer`], so parsing the printed text gives the same value back,
+/// but the whitespace and comments of the original source are not kept.
+///
+/// It is implemented for every type that implements [`Parse`](super::Parse) in this crate,
+/// and can be derived with `#[derive(Printable)]`, which prints the fields in the order that `#[derive(Parsable)]` parses them.
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, print::Printer, tokens, Parse, Group, List, StringValue, Number};
+/// # fn main() {
+/// 	#[cfg(feature="derive")]
+/// 	{
+/// 		# use parseal::{Parsable, Printable, parsing};
+/// 		#[derive(Parsable, Printable, Clone)]
+/// 		enum Value<'a> {
+/// 			Number(Number<'a>),
+/// 			String(StringValue<'a>),
+/// 			Array(Group<tokens::Bracket, List<Value<'a>, tokens::Comma>>),
+/// 			Object(Group<tokens::Brace, List<(StringValue<'a>, tokens::Colon, Value<'a>), tokens::Comma>>)
+/// 		}
+///
+/// 		let source = r#"{"a": [1, 2.5], "b": {"c": "d\n"}}"#;
+/// 		let value = Value::parse(&mut CharStream::new(source).build()).unwrap();
+/// 		let printed = Printer::new().print(&value);
+/// 		assert_eq!(printed, source);
+///
+/// 		let reparsed = Value::parse(&mut CharStream::new(&printed).build()).unwrap();
+/// 		assert_eq!(Printer::new().print(&reparsed), printed);
+/// 	}
+/// # }
+/// ```
+///
+/// With an [`Indent`], the same derives make a formatter for an indentation based format, like the YAML example:
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, print::Printer, tokens, Parse, Indent, Identifier, StringValue, Number};
+/// # fn main() {
+/// 	#[cfg(feature="derive")]
+/// 	{
+/// 		# use parseal::{Parsable, Printable, parsing};
+/// 		#[derive(Parsable, Printable, Clone)]
+/// 		enum Node<'a> {
+/// 			Mapping(Indent<(Identifier<'a>, tokens::Colon, Node<'a>)>),
+/// 			Sequence(Indent<(tokens::Hyphen, Node<'a>)>),
+/// 			String(StringValue<'a>),
+/// 			Number(Number<'a>)
+/// 		}
+///
+/// 		#[derive(Parsable, Printable, Clone)]
+/// 		struct Document<'a> {
+/// 			#[whitespace(KeepAll)]
+/// 			start: [tokens::Hyphen; 3],
+/// 			node: Node<'a>
+/// 		}
+///
+/// 		let source = "---\nname:   \"example\"\ninner:\n      numbers:\n         - 1\n         -   2\n      deeper:\n         value: 3\nlast: 4\n";
+/// 		let value = Document::parse(&mut CharStream::new(source).build()).unwrap();
+/// 		let printed = Printer::new().indent(2).print(&value);
+/// 		assert_eq!(printed, "---\nname: \"example\"\ninner:\n  numbers:\n    - 1\n    - 2\n  deeper:\n    value: 3\nlast: 4");
+///
+/// 		let reparsed = Document::parse(&mut CharStream::new(&printed).build()).unwrap();
+/// 		assert_eq!(Printer::new().indent(2).print(&reparsed), printed);
+/// 	}
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+pub trait Print {
+	fn print(&self, printer: &mut Printer);
+}
+
+/// What the printer puts between the previous text and the next one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Gap {
+	None,
+	Space,
+	/// A line break if the group it is in does not fit on the line, otherwise a space if the flag is set.
+	Break(bool),
+	/// Line breaks that are always written, like the ones at the end of a [`BlockScalar`] that are part of its value.
+	Lines(usize)
+}
+
+/// A Printer lays out the text of values that implement [`Print`], by building a [`Doc`] and rendering it at the line width.
+/// Texts are separated by a space, except after opening delimiters, before closing delimiters and before attached tokens like `,` and `:`.
+/// A [`Group`] is printed on one line if it fits, otherwise its items are each printed on their own line, one level deeper,
+/// with the closing delimiter on its own line. The items of an [`Indent`] are always printed on their own line.
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, print::Printer, tokens, Parse, Group, List, StringValue, Number};
+/// type Object<'a> = Group<tokens::Brace, List<(StringValue<'a>, tokens::Colon, Number<'a>), tokens::Comma>>;
+///
+/// # fn main() {
+/// 	let value = Object::parse(&mut CharStream::new("{ \"a\" :1,\"b\":  2 }").build()).unwrap();
+/// 	let printed = Printer::new().print(&value);
+/// 	assert_eq!(printed, "{\"a\": 1, \"b\": 2}");
+///
+/// 	let reparsed = Object::parse(&mut CharStream::new(&printed).build()).unwrap();
+/// 	assert_eq!(Printer::new().print(&reparsed), printed);
+///
+/// 	let narrow = Printer::new().indent(2).separator_spacing(false).line_width(10).print(&value);
+/// 	assert_eq!(narrow, "{\n  \"a\": 1,\n  \"b\": 2\n}");
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+#[derive(Debug, Clone)]
+pub struct Printer {
+	indent: usize,
+	separator_spacing: bool,
+	line_width: usize,
+	attached: Vec<String>,
+	docs: Vec<Vec<Doc>>,
+	gap: Gap,
+	tight: bool,
+	source: Option<String>,
+	/// The end of the source text that was written in lossless mode.
+	written: usize,
+	/// Whether nothing was written since the start or the last [`Printer::line`].
+	line_start: bool
+}
+
+impl Default for Printer {
+	fn default() -> Self {
+		Self {
+			indent: 4,
+			separator_spacing: true,
+			line_width: 80,
+			attached: vec![",".to_owned(), ";".to_owned(), ":".to_owned()],
+			docs: vec![Vec::new()],
+			gap: Gap::None,
+			tight: false,
+			source: None,
+			written: 0,
+			line_start: true
+		}
+	}
+}
+
+impl Printer {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the number of spaces of one level of indentation.
+	pub fn indent(&mut self, indent: usize) -> &mut Self {
+		self.indent = indent;
+		self
+	}
+
+	/// Sets whether there is a space after the separators of a [`List`], like in `[1, 2]`.
+	pub fn separator_spacing(&mut self, spacing: bool) -> &mut Self {
+		self.separator_spacing = spacing;
+		self
+	}
+
+	/// Sets the width that the groups have to fit in to be printed on one line.
+	pub fn line_width(&mut self, width: usize) -> &mut Self {
+		self.line_width = width;
+		self
+	}
+
+	/// Sets the tokens that are attached to the text before them, without a space in between.
+	pub fn attach(&mut self, tokens: &[&str]) -> &mut Self {
+		self.attached = tokens.iter().map(|token| token.to_string()).collect();
+		self
+	}
+
+	/// Prints values exactly as they were written in `source`, including the whitespace and comments around every token.
+	/// Every leaf, like a token, a string or a number, writes its source text together with its [`Trivia`] instead of being laid out,
+	/// so the value has to be parsed from `source` with a [`lossless`](super::charstream::CharStreamBuilder::lossless) stream.
+	/// Items that were replaced by an [`ErrorNode`] in recovery mode are written as well,
+	/// and so is the source after the last leaf, like the comments at the end of a file.
+	/// ```
+	/// # use parseal::parsing::{charstream::CharStream, print::Printer, tokens, Parse, Group, List, Number};
+	/// type Matrix<'a> = Group<tokens::Bracket, List<Group<tokens::Bracket, List<Number<'a>, tokens::Comma>>, tokens::Comma>>;
+	///
+	/// # fn main() {
+	/// 	let source = "[ // rows\n  [1,2] , /* the second row */ [ 3 ,4 ]\n] // done\n";
+	/// 	let mut buffer = CharStream::new(source).line_comment("//").block_comment("/*", "*/").lossless(true).build();
+	/// 	let value = Matrix::parse(&mut buffer).unwrap();
+	///
+	/// 	assert_eq!(Printer::new().lossless(source).print(&value), source);
+	/// 	assert_eq!(Printer::new().print(&value), "[[1, 2], [3, 4]]");
+	///
+	/// 	let source = "[[0, x], [2 // two\n, 3]]";
+	/// 	let mut buffer = CharStream::new(source).line_comment("//").recover(true).lossless(true).build();
+	/// 	let value = Matrix::parse(&mut buffer).unwrap();
+	///
+	/// 	assert_eq!(Printer::new().lossless(source).print(&value), source);
+	/// 	assert_eq!(Printer::new().print(&value), "[[0], [2, 3]]");
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn lossless(&mut self, source: &str) -> &mut Self {
+		self.source = Some(source.to_owned());
+		self
+	}
+
+	pub fn is_lossless(&self) -> bool {
+		self.source.is_some()
+	}
+
+	/// Prints `value` and returns the text.
+	pub fn print<T: Print + ?Sized>(&mut self, value: &T) -> String {
+		self.doc(value).render(self.line_width)
+	}
+
+	/// Prints `value` into a [`Doc`] instead of text, so it can be combined with other docs before it is rendered.
+	/// The whole value is one group, so its line breaks are flat if it fits on one line.
+	pub fn doc<T: Print + ?Sized>(&mut self, value: &T) -> Doc {
+		self.docs = vec![Vec::new()];
+		self.gap = Gap::None;
+		self.written = 0;
+		self.line_start = true;
+
+		value.print(self);
+		if let Gap::Lines(lines) = self.gap {
+			self.push(Doc::concat(vec![Doc::hardline(); lines]));
+		}
+		if let Some(rest) = self.source.as_ref().and_then(|source| source.get(self.written..)).filter(|rest| !rest.is_empty()) {
+			let rest = Doc::text(rest);
+			self.push(rest);
+		}
+		Doc::group(Doc::concat(self.docs.pop().unwrap_or_default()))
+	}
+
+	/// Writes a piece of text, after the space that the previous text left.
+	pub fn text(&mut self, text: &str) {
+		match if self.is_lossless() { Gap::None } else { self.gap } {
+			Gap::None => {}
+			Gap::Space => self.push(Doc::text(" ")),
+			Gap::Break(true) => self.push(Doc::line()),
+			Gap::Break(false) => self.push(Doc::softline()),
+			Gap::Lines(lines) => self.push(Doc::concat(vec![Doc::hardline(); lines]))
+		}
+
+		self.push(Doc::text(text));
+		self.gap = if self.tight { Gap::None } else { Gap::Space };
+		self.line_start = false;
+	}
+
+	/// Writes a token, which is attached to the text before it if it is one of the attached tokens.
+	pub fn token(&mut self, token: &str) {
+		if self.attached.iter().any(|attached| attached == token) {
+			self.glue();
+		}
+		self.text(token);
+	}
+
+	/// Removes the space before the next text.
+	pub fn glue(&mut self) {
+		self.gap = Gap::None;
+	}
+
+	/// Allows a line break before the next text, this is used after separators.
+	pub fn allow_break(&mut self) {
+		self.gap = Gap::Break(self.separator_spacing);
+	}
+
+	/// Allows a line break before the next text, without a space when the group is on one line.
+	/// This is used inside delimiters.
+	pub fn soft_break(&mut self) {
+		self.gap = Gap::Break(false);
+	}
+
+	/// Starts a new line at the current indentation, which also breaks the groups around it.
+	/// In lossless mode the line breaks come from the trivia, so this does nothing.
+	pub fn line(&mut self) {
+		if self.is_lossless() {
+			return;
+		}
+		let lines = match self.gap {
+			Gap::Lines(lines) => lines,
+			_ => 1
+		};
+		self.push(Doc::concat(vec![Doc::hardline(); lines]));
+		self.gap = Gap::None;
+		self.line_start = true;
+	}
+
+	/// Returns whether nothing was written on the current line yet.
+	pub fn is_line_start(&self) -> bool {
+		self.line_start
+	}
+
+	/// Prints with one more level of indentation.
+	pub fn nest(&mut self, print: impl FnOnce(&mut Printer)) {
+		let indent = self.indent;
+		let doc = self.collect(print);
+		self.push(Doc::nest(indent, doc));
+	}
+
+	/// Prints a group, whose line breaks are either all flat or all broken.
+	pub fn group(&mut self, print: impl FnOnce(&mut Printer)) {
+		let doc = self.collect(print);
+		self.push(Doc::group(doc));
+	}
+
+	/// Prints a leaf of the tree, like a token, with `print`.
+	/// In lossless mode its source text is written instead, from the start of its leading trivia to the end of its trailing trivia.
+	pub fn leaf(&mut self, trivia: Trivia, print: impl FnOnce(&mut Printer)) {
+		let text = match &self.source {
+			Some(source) => source.get(trivia.leading.start.offset..trivia.trailing.end.offset).unwrap_or_default().to_owned(),
+			None => return print(self)
+		};
+		self.written = self.written.max(trivia.trailing.end.offset);
+		self.push(Doc::Text(text));
+		self.line_start = false;
+	}
+
+	/// Writes `lines` line breaks before the next text, or at the end, instead of the space that would be there.
+	/// A [`line`](Self::line) right after this starts the next text on the last of these lines instead of adding another one.
+	pub fn break_lines(&mut self, lines: usize) {
+		if lines > 0 {
+			self.gap = Gap::Lines(lines);
+		}
+	}
+
+	/// Prints without space between the texts, for values that are parsed with [`WhitespaceType::KeepAll`](super::charstream::WhitespaceType::KeepAll).
+	/// The derived implementation uses this for fields with the `#[whitespace(KeepAll)]` attribute.
+	/// ```
+	/// # use parseal::parsing::{charstream::CharStream, print::Printer, tokens, Parse, Number};
+	/// # fn main() {
+	/// 	#[cfg(feature="derive")]
+	/// 	{
+	/// 		# use parseal::{Parsable, Printable, parsing};
+	/// 		#[derive(Parsable, Printable, Clone)]
+	/// 		struct Rule<'a>(#[whitespace(KeepAll)] [tokens::Hyphen; 3], Number<'a>);
+	///
+	/// 		let value = Rule::parse(&mut CharStream::new("---   3").build()).unwrap();
+	/// 		let printed = Printer::new().print(&value);
+	/// 		assert_eq!(printed, "--- 3");
+	///
+	/// 		let reparsed = Rule::parse(&mut CharStream::new(&printed).build()).unwrap();
+	/// 		assert_eq!(reparsed.1.text(), "3");
+	/// 		assert_eq!(Printer::new().print(&reparsed), printed);
+	/// 	}
+	/// # }
+	/// ```
+	#[allow(clippy::tabs_in_doc_comments)]
+	pub fn tight(&mut self, print: impl FnOnce(&mut Printer)) {
+		let tight = self.tight;
+		self.tight = true;
+		print(self);
+		self.tight = tight;
+		if !tight {
+			self.gap = Gap::Space;
+		}
+	}
+
+	/// Appends a doc, so values can lay themselves out with docs they built.
+	pub fn push(&mut self, doc: Doc) {
+		if let Some(docs) = self.docs.last_mut() {
+			docs.push(doc);
+		}
+	}
+
+	fn collect(&mut self, print: impl FnOnce(&mut Printer)) -> Doc {
+		self.docs.push(Vec::new());
+		print(self);
+		Doc::concat(self.docs.pop().unwrap_or_default())
+	}
+}
+
+impl<D, I> Print for Group<D, I> where D: Delimiter, I: Print {
+	fn print(&self, printer: &mut Printer) {
+		printer.group(|printer| {
+			self.delimiters().start().print(printer);
+			printer.soft_break();
+			printer.nest(|printer| match self.inner() {
+				Ok(inner) => inner.print(printer),
+				Err(node) => node.print(printer)
+			});
+			printer.soft_break();
+			self.delimiters().end().print(printer);
+		});
+	}
+}
+
+/// The items that failed to parse in recovery mode are left out, together with their separators, unless the printer is lossless.
+impl<I, S> Print for List<I, S> where I: Print, S: Token {
+	fn print(&self, printer: &mut Printer) {
+		let lossless = printer.is_lossless();
+		let entries = self.entries().filter(|(item, _)| lossless || item.is_ok()).collect::<Vec<_>>();
+		let count = entries.len();
+
+		for (index, (item, separator)) in entries.into_iter().enumerate() {
+			match item {
+				Ok(item) => item.print(printer),
+				Err(node) => node.print(printer)
+			}
+
+			// the separator of the last item that is printed would leave the list without an item after it.
+			if let Some(separator) = separator.filter(|_| lossless || index + 1 < count) {
+				printer.glue();
+				separator.print(printer);
+				printer.allow_break();
+			}
+		}
+	}
+}
+
+/// Every item is printed on its own line. An Indent that starts after text on the same line, like the value of `first:` below,
+/// has its items one level deeper on the lines below, unless they were at the start of their lines in the source,
+/// like the top level of a YAML document after `---`.
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, print::Printer, tokens, Parse, Indent, Identifier, Number};
+/// type Mapping<'a> = Indent<(Identifier<'a>, tokens::Colon, Indent<(Identifier<'a>, tokens::Colon, Number<'a>)>)>;
+///
+/// # fn main() {
+/// 	let source = "first:\n  a: 1\n  b:   2\nsecond:\n     c: 3\n";
+/// 	let value = Mapping::parse(&mut CharStream::new(source).build()).unwrap();
+/// 	let printed = Printer::new().indent(2).print(&value);
+/// 	assert_eq!(printed, "first:\n  a: 1\n  b: 2\nsecond:\n  c: 3");
+///
+/// 	let reparsed = Mapping::parse(&mut CharStream::new(&printed).build()).unwrap();
+/// 	assert_eq!(reparsed.iter().map(|(key, _, values)| (key.as_str(), values.iter().count())).collect::<Vec<_>>(), vec![("first", 2), ("second", 1)]);
+/// 	assert_eq!(Printer::new().indent(2).print(&reparsed), printed);
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+impl<T> Print for Indent<T> where T: Print {
+	fn print(&self, printer: &mut Printer) {
+		let line_start = printer.is_line_start();
+		let print = |printer: &mut Printer| for (index, item) in self.iter().enumerate() {
+			if index > 0 || !line_start {
+				printer.line();
+			}
+			item.print(printer);
+		};
+
+		if line_start || self.depth() == 0 {
+			print(printer);
+		} else {
+			printer.nest(print);
+		}
+	}
+}
+
+impl<Q> Print for QuotedString<'_, Q> where Q: Delimiter {
+	fn print(&self, printer: &mut Printer) {
+		printer.leaf(self.trivia(), |printer| printer.text(&format!("{}{}{}", Q::OPEN, self.raw(), Q::CLOSE)));
+	}
+}
+
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, print::Printer, Parse, RawString};
+/// # fn main() {
+/// 	let source = r###"r#"a "quoted" \n"#"###;
+/// 	let value = RawString::parse(&mut CharStream::new(source).build()).unwrap();
+/// 	let printed = Printer::new().print(&value);
+/// 	assert_eq!(printed, source);
+///
+/// 	let reparsed = RawString::parse(&mut CharStream::new(&printed).build()).unwrap();
+/// 	assert_eq!(reparsed.value(), value.value());
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+impl Print for RawString<'_> {
+	fn print(&self, printer: &mut Printer) {
+		let hashes = "#".repeat(self.hashes());
+		printer.leaf(self.trivia(), |printer| printer.text(&format!("r{}\"{}\"{}", hashes, self.value(), hashes)));
+	}
+}
+
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, print::Printer, Parse, MultilineString};
+/// # fn main() {
+/// 	let source = "\"\"\"\nfirst\n  second \\\n  \"line\"\"\"\"";
+/// 	let value = MultilineString::parse(&mut CharStream::new(source).build()).unwrap();
+/// 	let printed = Printer::new().print(&value);
+/// 	assert_eq!(printed, source);
+///
+/// 	let reparsed = MultilineString::parse(&mut CharStream::new(&printed).build()).unwrap();
+/// 	assert_eq!(reparsed.value(), "first\n  second \"line\"");
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+impl<Q> Print for TripleQuotedString<'_, Q> where Q: Delimiter {
+	fn print(&self, printer: &mut Printer) {
+		printer.leaf(self.trivia(), |printer| printer.text(&format!("{}{}{}", Q::OPEN.repeat(3), self.raw(), Q::CLOSE.repeat(3))));
+	}
+}
+
+/// The lines of the block are indented one level deeper than the line the block starts on.
+/// The line breaks at the end of the value are written after it, so the value does not change when the printed text is parsed again.
+/// ```
+/// # use parseal::parsing::{charstream::CharStream, print::Printer, tokens, Parse, BlockScalar, Indent, Identifier};
+/// type Scripts<'a> = Indent<(Identifier<'a>, tokens::Colon, BlockScalar<'a>)>;
+///
+/// # fn main() {
+/// 	let source = "build: |\n  make\n\n  make install\nkeep: |+\n  line\n\nstrip: >-\n  folded\n  text\n";
+/// 	let value = Scripts::parse(&mut CharStream::new(source).build()).unwrap();
+/// 	let printed = Printer::new().indent(2).print(&value);
+/// 	assert_eq!(printed, "build: |\n  make\n\n  make install\nkeep: |+\n  line\n\nstrip: >-\n  folded\n  text");
+///
+/// 	let values = |scripts: &Scripts| scripts.iter().map(|(_, _, block)| block.value().to_owned()).collect::<Vec<_>>();
+/// 	let reparsed = Scripts::parse(&mut CharStream::new(&printed).build()).unwrap();
+/// 	assert_eq!(values(&reparsed), vec!["make\n\nmake install\n", "line\n\n", "folded text"]);
+/// 	assert_eq!(values(&reparsed), values(&value));
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+impl Print for BlockScalar<'_> {
+	fn print(&self, printer: &mut Printer) {
+		let mut lines = self.raw().split('\n');
+		let header = lines.next().unwrap_or_default().trim_end();
+		let lines = lines.map(|line| line.trim_end_matches('\r')).collect::<Vec<_>>();
+		let common = lines.iter()
+			.filter(|line| !line.trim().is_empty())
+			.map(|line| line.len() - line.trim_start_matches(' ').len())
+			.min()
+			.unwrap_or(0);
+		// the line breaks of an empty block that keeps them with `+` start after the line of the header.
+		let breaks = self.value().len() - self.value().trim_end_matches('\n').len();
+		let breaks = breaks + usize::from(lines.is_empty() && breaks > 0);
+
+		printer.leaf(self.trivia(), |printer| {
+			printer.text(header);
+			printer.nest(|printer| for line in lines {
+				printer.line();
+				if !line.trim().is_empty() {
+					printer.text(&line[common..]);
+				}
+			});
+
+			printer.break_lines(breaks);
+		});
+	}
+}
+
+/// The input that an ErrorNode skipped is only printed in lossless mode, otherwise it prints nothing.
+impl Print for ErrorNode {
+	fn print(&self, printer: &mut Printer) {
+		printer.leaf(self.trivia(), |_| {});
+	}
+}
+
+impl<D> Print for NumberLiteral<'_, D> where D: NumberDialect {
+	fn print(&self, printer: &mut Printer) {
+		printer.leaf(self.trivia(), |printer| printer.text(self.text()));
+	}
+}
+
+impl<R> Print for Ident<'_, R> where R: IdentifierRules {
+	fn print(&self, printer: &mut Printer) {
+		printer.leaf(self.trivia(), |printer| printer.text(self.as_str()));
+	}
+}
+
+impl<T> Print for Vec<T> where T: Print {
+	fn print(&self, printer: &mut Printer) {
+		for item in self {
+			item.print(printer);
+		}
+	}
+}
+
+impl<T, const N: usize> Print for [T; N] where T: Print {
+	fn print(&self, printer: &mut Printer) {
+		for item in self {
+			item.print(printer);
+		}
+	}
+}
+
+impl Print for () {
+	fn print(&self, _printer: &mut Printer) {}
+}
+
+/// Implements Print for a tuple, which prints its elements in order.
+macro_rules! print_tuple {
+	($($name:ident $index:tt),+) => {
+		impl<$($name),+> Print for ($($name,)+) where $($name: Print),+ {
+			fn print(&self, printer: &mut Printer) {
+				$(self.$index.print(printer);)+
+			}
+		}
+	};
+}
+
+print_tuple!(A 0);
+print_tuple!(A 0, B 1);
+print_tuple!(A 0, B 1, C 2);
+print_tuple!(A 0, B 1, C 2, D 3);
+print_tuple!(A 0, B 1, C 2, D 3, E 4);
+print_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+print_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+print_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+print_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+print_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+print_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+print_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
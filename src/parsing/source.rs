@@ -0,0 +1,134 @@
+use std::fmt;
+
+use super::charstream::{CharStream, CharStreamBuilder, LineCol, LineIndex, Position, Span};
+
+/// A SourceFile is a source that was registered in a [`SourceMap`].
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+	id: u32,
+	name: String,
+	text: String,
+	lines: LineIndex
+}
+
+impl SourceFile {
+	pub fn id(&self) -> u32 {
+		self.id
+	}
+
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	pub fn text(&self) -> &str {
+		&self.text
+	}
+
+	pub fn lines(&self) -> &LineIndex {
+		&self.lines
+	}
+
+	/// Returns the line and column of `position` in this source, with the column counted in characters.
+	pub fn line_col(&self, position: Position) -> LineCol {
+		self.lines.line_col(&self.text, position.offset.min(self.text.len()))
+	}
+}
+
+/// A Location is a [`Position`] resolved to the file it is in and the line and column in that file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location<'a> {
+	pub file: &'a str,
+	pub line_col: LineCol
+}
+
+impl fmt::Display for Location<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}:{}", self.file, self.line_col)
+	}
+}
+
+/// A SourceMap owns the text of every source that is parsed, and hands out a sequential id for each of them, starting at 1.
+/// The ids are stored in every [`Position`], so a [`Span`] can be resolved back to the file it came from.
+/// The id 0 is never handed out, it is the id of streams that were not created through a source map.
+///
+/// Parsed values borrow from the map, so the text that is needed to add another source, like the path of an included file, is copied out first.
+/// ```
+/// # use parseal::parsing::{source::SourceMap, charstream::CharStream, tokens, Parse, Number, StringValue};
+/// # fn main() {
+/// 	let mut sources = SourceMap::new();
+/// 	let main = sources.add("main.conf", "\"include.conf\", 2".to_owned());
+///
+/// 	let mut buffer = sources.stream(main).unwrap().build();
+/// 	let (path, _) = <(StringValue, tokens::Comma)>::parse(&mut buffer).unwrap();
+/// 	let path = path.value().to_owned();
+///
+/// 	let include = sources.add(&path, "3\n4".to_owned());
+/// 	assert_eq!((main, include), (1, 2));
+///
+/// 	let mut buffer = sources.stream(include).unwrap().build();
+/// 	let error = <(Number, tokens::Comma)>::parse(&mut buffer).unwrap_err();
+///
+/// 	assert_eq!(sources.resolve(error.span().start).unwrap().to_string(), "include.conf:2:1");
+/// 	assert_eq!(sources.snippet(error.span()), Some("4"));
+/// 	assert_eq!(error.display_with(&sources), "expected `,`, found `4` at include.conf:2:1");
+///
+/// 	let standalone = CharStream::new("1").build();
+/// 	assert!(sources.file(standalone.position()).is_none());
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+	files: Vec<SourceFile>
+}
+
+impl SourceMap {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Registers a source and returns its id.
+	pub fn add(&mut self, name: &str, text: String) -> u32 {
+		let id = self.files.len() as u32 + 1;
+		let lines = LineIndex::new(&text);
+		self.files.push(SourceFile { id, name: name.to_owned(), text, lines });
+		id
+	}
+
+	pub fn get(&self, id: u32) -> Option<&SourceFile> {
+		self.files.get((id as usize).checked_sub(1)?)
+	}
+
+	pub fn files(&self) -> &[SourceFile] {
+		&self.files
+	}
+
+	/// Returns a builder for a [`CharStream`] over the source with the given id,
+	/// with its file name and source id already set.
+	pub fn stream(&self, id: u32) -> Option<CharStreamBuilder<'_>> {
+		let file = self.get(id)?;
+		let mut builder = CharStream::new(&file.text);
+		builder.file(&file.name).source_id(id);
+		Some(builder)
+	}
+
+	/// Returns the file that `position` is in.
+	pub fn file(&self, position: Position) -> Option<&SourceFile> {
+		self.get(position.source_id)
+	}
+
+	/// Resolves `position` to the name of its file and the line and column in that file.
+	pub fn resolve(&self, position: Position) -> Option<Location<'_>> {
+		let file = self.file(position)?;
+		Some(Location { file: &file.name, line_col: file.line_col(position) })
+	}
+
+	/// Returns the text that `span` covers.
+	pub fn snippet(&self, span: &Span) -> Option<&str> {
+		if span.start.source_id != span.end.source_id {
+			return None;
+		}
+
+		self.file(span.start)?.text.get(span.range())
+	}
+}
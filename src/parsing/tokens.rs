@@ -1,159 +1,333 @@
 use std::fmt;
 
-use super::{Parse, ParseError, charstream::CharStream};
+use super::{Parse, ParseError, Expected, identifier, print::Print, charstream::{CharStream, Position, Span, Trivia, WhitespaceType}};
 
-pub trait Token: Parse + fmt::Display {
+/// A Token borrows nothing from the source, so it can be parsed from a [`CharStream`] of any lifetime.
+pub trait Token: for<'src> Parse<'src> + Print + fmt::Display {
+    /// Returns the trivia around the token, which is only recorded in a lossless stream.
+    fn trivia(&self) -> Trivia;
 
+    /// Creates the token without any text at `position`, for a token that is missing in the source, like the closing delimiter that a [`Group`](super::Group) inserts in recovery mode.
+    fn missing(position: Position) -> Self;
 }
 
 pub trait Delimiter: Clone {
 	type Start: Token;
 	type End: Token;
 
+	/// The text of the start of the delimiter, like `(` or `<!--`.
+	const OPEN: &'static str;
+	/// The text of the end of the delimiter, which can differ in length from the start, like `-->`.
+	const CLOSE: &'static str;
+
 	fn new(start: Self::Start, end: Self::End) -> Self where Self: Sized;
+	fn start(&self) -> &Self::Start;
+	fn end(&self) -> &Self::End;
     fn span(&self) -> super::Span;
     fn name() -> String;
 }
 
-macro_rules! create_tokens {
-    ($($token:tt $id:ident),+) => {
+/// Parses `keyword` as a whole word, and returns its span and trivia.
+/// The keyword has to end on an identifier boundary, so `true` does not match the start of `trueish`.
+/// This is the parser behind the types that [`create_keywords!`](crate::create_keywords) creates.
+pub fn keyword(value: &mut CharStream<'_>, keyword: &str) -> Result<(Span, Trivia), ParseError> {
+	let checkpoint = value.checkpoint();
+	let leading = value.leading_trivia();
+	let start = value.position();
+
+	let rest = &value.source()[value.offset()..];
+	let len = rest.char_indices()
+		.find(|(_, chr)| !identifier::continues::<identifier::Rust>(*chr))
+		.map_or(rest.len(), |(index, _)| index);
+
+	let expected = Expected::Token(keyword.to_owned());
+	if &rest[..len] == keyword {
+		consume(value, keyword);
+		let span = Span::new(start, value.position());
+		return Ok((span, Trivia::new(leading, value.trailing_trivia())));
+	}
+
+	let error = match rest.chars().next() {
+		Some(chr) => {
+			let found = if len == 0 { &rest[..chr.len_utf8()] } else { &rest[..len] };
+			let end = super::Position::new(start.offset + found.len(), start.source_id);
+			ParseError::unexpected(expected, found, Span::new(start, end))
+		}
+		None => ParseError::eof(expected, start)
+	};
+
+	value.rewind(checkpoint);
+	Err(error)
+}
+
+/// Parses `token` with its characters next to each other, and returns its span and trivia.
+/// With `longest`, the match is refused when a longer token matches as well, so `=` does not match the start of `==`.
+/// The longer tokens are the ones in `longest` and the ones registered with the stream by [`CharStreamBuilder::tokens`](super::charstream::CharStreamBuilder::tokens),
+/// since a token only knows the other tokens of its own [`create_tokens!`](crate::create_tokens) invocation.
+/// A token that starts and ends with a letter or digit, like `and`, has to end on an identifier boundary.
+/// This is the parser behind the tokens in this module, which all match the longest token.
+/// ```
+/// # use parseal::parsing::{charstream::{CharStream, WhitespaceType}, tokens, Parse, Number};
+/// # fn main() {
+/// 	assert!(tokens::Equal::parse(&mut CharStream::new("==").build()).is_err());
+/// 	assert!(tokens::EqualEqual::parse(&mut CharStream::new("==").build()).is_ok());
+/// 	assert!(tokens::EqualEqual::parse(&mut CharStream::new("= =").build()).is_err());
+///
+/// 	let mut buffer = CharStream::new("= =").build();
+/// 	assert!(<(tokens::Equal, tokens::Equal)>::parse(&mut buffer).is_ok());
+///
+/// 	let mut buffer = CharStream::new("::").build();
+/// 	assert!(tokens::token(&mut buffer, ":", None).is_ok());
+/// 	let mut buffer = CharStream::new("::").build();
+/// 	assert!(tokens::token(&mut buffer, ":", Some(&["::"])).is_err());
+///
+/// 	// with WhitespaceType::Indent, only the spaces before the first token of a line are its indentation.
+/// 	let mut buffer = CharStream::new("  -   1").build();
+/// 	buffer.set_whitespace(WhitespaceType::Indent);
+/// 	<(tokens::Hyphen, Number)>::parse(&mut buffer).unwrap();
+/// 	assert_eq!(buffer.indent(), 2);
+/// # }
+/// ```
+#[allow(clippy::tabs_in_doc_comments)]
+pub fn token(value: &mut CharStream<'_>, token: &str, longest: Option<&[&str]>) -> Result<(Span, Trivia), ParseError> {
+	let checkpoint = value.checkpoint();
+	let leading = value.leading_trivia();
+	let start = value.position();
+
+	let rest = &value.source()[value.offset()..];
+	let longer = longest.map(|registered| registered.iter().copied().chain(value.tokens().iter().map(String::as_str)))
+		.into_iter()
+		.flatten()
+		.filter(|other| other.len() > token.len() && other.starts_with(token) && rest.starts_with(*other))
+		.map(str::len)
+		.max();
+	let is_word = |chr: Option<char>| chr.is_some_and(char::is_alphanumeric);
+	let joined = is_word(token.chars().next()) && is_word(token.chars().last())
+		&& rest.get(token.len()..).is_some_and(|after| after.starts_with(identifier::continues::<identifier::Rust>));
+
+	let expected = Expected::Token(token.to_owned());
+	if rest.starts_with(token) && longer.is_none() && !joined {
+		consume(value, token);
+		let span = Span::new(start, value.position());
+		return Ok((span, Trivia::new(leading, value.trailing_trivia())));
+	}
+
+	let found = match longer {
+		Some(longer) => &rest[..longer],
+		None => rest.char_indices()
+			.map(|(index, chr)| &rest[..index + chr.len_utf8()])
+			.find(|found| found.len() >= token.len())
+			.unwrap_or(rest)
+	};
+	let error = if found.is_empty() {
+		ParseError::eof(expected, start)
+	} else {
+		ParseError::unexpected(expected, found, Span::new(start, super::Position::new(start.offset + found.len(), start.source_id)))
+	};
+
+	value.rewind(checkpoint);
+	Err(error)
+}
+
+/// Moves the stream past `text`, which has to be next in the stream.
+/// The first character is read in the mode of the stream, so in [`WhitespaceType::Indent`] it ends the indentation of its line,
+/// and the rest is read without skipping anything.
+fn consume(value: &mut CharStream<'_>, text: &str) {
+	let mut chars = text.chars();
+	if chars.next().is_some() {
+		value.next();
+	}
+
+	let whitespace = value.whitespace();
+	value.set_whitespace(WhitespaceType::KeepAll);
+	for _ in chars {
+		value.next();
+	}
+	value.set_whitespace(whitespace);
+}
+
+/// Creates a [`Token`] type for every keyword, which only matches the keyword as a whole word.
+/// ```
+/// # use parseal::{create_keywords, parsing::{charstream::CharStream, print::Printer, tokens, Parse, Identifier}};
+/// create_keywords! {
+///     "true" True,
+///     "false" False,
+///     "let" Let
+/// }
+///
+/// # fn main() {
+///     assert!(True::parse(&mut CharStream::new(" true").build()).is_ok());
+///     assert!(True::parse(&mut CharStream::new("trueish").build()).is_err());
+///     assert!(False::parse(&mut CharStream::new("true").build()).is_err());
+///
+///     let value = <(Let, Identifier, tokens::Equal, True)>::parse(&mut CharStream::new("let  flag=true").build()).unwrap();
+///     let printed = Printer::new().print(&value);
+///     assert_eq!(printed, "let flag = true");
+///
+///     let reparsed = <(Let, Identifier, tokens::Equal, True)>::parse(&mut CharStream::new(&printed).build()).unwrap();
+///     assert_eq!(reparsed.3.span().start.offset, 11);
+///     assert_eq!(Printer::new().print(&reparsed), printed);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! create_keywords {
+    ($($keyword:literal $id:ident),+ $(,)?) => {
         $(
             #[derive(Clone)]
             pub struct $id {
-                span: super::Span
+                span: $crate::parsing::charstream::Span,
+                trivia: $crate::parsing::charstream::Trivia
             }
-            
-            impl Token for $id {}
-            
-            impl Parse for $id {
-                fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized {
-                    let token = stringify!($token);
-                    let len = token.len();
-                    let start = value.position();
 
-                    let mut token_value = value.clone();
+            impl $crate::parsing::tokens::Token for $id {
+                fn trivia(&self) -> $crate::parsing::charstream::Trivia {
+                    self.trivia
+                }
 
-                    let mut mtch = String::new();
-                    while mtch.len() < len {
-                        mtch.push(match token_value.next() {
-                            Some(value) => value,
-                            None => break
-                        });
-                    }
+                fn missing(position: $crate::parsing::charstream::Position) -> Self {
+                    let span = $crate::parsing::charstream::Span::new(position, position);
+                    Self { span, trivia: $crate::parsing::charstream::Trivia::new(span, span) }
+                }
+            }
 
-                    if (token == mtch) {
-                        value.goto(token_value.position())?;
-                        let end = value.position();
-                        return Ok(Self { span: super::Span::new(start, end)});
-                    }
+            impl<'src> $crate::parsing::Parse<'src> for $id {
+                fn parse(value: &mut $crate::parsing::charstream::CharStream<'src>) -> ::std::result::Result<Self, $crate::parsing::ParseError> {
+                    $crate::parsing::tokens::keyword(value, $keyword).map(|(span, trivia)| Self { span, trivia })
+                }
 
-                    Err(ParseError(format!("Could not find token '{}'.", stringify!($token)), token_value.position()))
+                fn span(&self) -> $crate::parsing::charstream::Span {
+                    self.span
                 }
+            }
 
-                fn span(&self) -> super::Span {
-                    self.span.clone()
+            impl ::std::fmt::Debug for $id {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    write!(f, "Keyword({}, at: {})", $keyword, self.span.end)
                 }
             }
 
-            impl fmt::Debug for $id {
-                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
-                    write!(f, "Token({}, at: {})", stringify!($token), self.span.end)
+            impl ::std::fmt::Display for $id {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    write!(f, "Keyword({})", $keyword)
                 }
             }
 
-            impl fmt::Display for $id {
-                fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-                    write!(f, "Token({})", stringify!($token))
+            impl $crate::parsing::print::Print for $id {
+                fn print(&self, printer: &mut $crate::parsing::print::Printer) {
+                    printer.leaf(self.trivia, |printer| printer.token($keyword));
                 }
             }
         )+
     };
 }
 
-macro_rules! create_delimiters {
-    ($($token:tt $left: ident $right: ident $delim:ident),+) => {
+/// Creates a [`Token`] type for every token, which matches the characters of the token next to each other.
+/// A token is written as itself, like `->`, or as a string literal when it can't be written on its own in Rust, like `"'"`.
+/// A token that is followed by `longest` refuses to match when a longer token of the same invocation matches, see [`token`].
+/// Tokens of other invocations, like the ones in this module, are not known to it unless they are registered with [`CharStreamBuilder::tokens`](crate::parsing::charstream::CharStreamBuilder::tokens).
+/// ```
+/// # use parseal::{create_tokens, parsing::{charstream::CharStream, Parse}};
+/// create_tokens! {
+///     - Minus longest,
+///     -> Arrow,
+///     < Less,
+///     <- LeftArrow,
+///     :: PathSep,
+///     "'" Apostrophe,
+///     "`" Backtick
+/// }
+///
+/// # fn main() {
+///     assert!(Arrow::parse(&mut CharStream::new("->").build()).is_ok());
+///     assert!(Minus::parse(&mut CharStream::new("->").build()).is_err());
+///     assert!(Less::parse(&mut CharStream::new("<-").build()).is_ok());
+///     assert!(<(Backtick, Apostrophe)>::parse(&mut CharStream::new("` '").build()).is_ok());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! create_tokens {
+    (@text -) => { "-" };
+    (@text $token:literal) => { $token };
+    (@text $token:tt) => { stringify!($token) };
+    (@longest $registered:tt longest) => { ::std::option::Option::Some(&$registered) };
+    (@longest $registered:tt) => { ::std::option::Option::None };
+    ($($token:tt $id:ident $($longest:ident)?),+ $(,)?) => {
+        $crate::create_tokens!(@registered [$($crate::create_tokens!(@text $token)),+] $($token $id $($longest)?),+);
+    };
+    (@registered $registered:tt $($token:tt $id:ident $($longest:ident)?),+) => {
         $(
             #[derive(Clone)]
-            pub struct $left {
-                span: super::Span
+            pub struct $id {
+                span: $crate::parsing::charstream::Span,
+                trivia: $crate::parsing::charstream::Trivia
             }
-
-            impl Token for $left {}
-
-            impl Parse for $left {
-                fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized {
-                    let chr = stringify!($token).chars().nth(0).unwrap();
-                    let mut token_value = value.clone();
-                    let start = value.position();
-
-                    if let Some(token) = token_value.next() {
-                        if token == chr {
-                            value.goto(token_value.position())?;
-                            let end = value.position();
-                            return Ok(Self { span: super::Span::new(start, end)})
-                        }
-                    }
-
-                    Err(ParseError(format!("could not find left side of: '{}'.", stringify!($token)), value.position()))
+            
+            impl $crate::parsing::tokens::Token for $id {
+                fn trivia(&self) -> $crate::parsing::charstream::Trivia {
+                    self.trivia
                 }
 
-                fn span(&self) -> super::Span {
-                    self.span.clone()
+                fn missing(position: $crate::parsing::charstream::Position) -> Self {
+                    let span = $crate::parsing::charstream::Span::new(position, position);
+                    Self { span, trivia: $crate::parsing::charstream::Trivia::new(span, span) }
                 }
             }
-
-            impl fmt::Debug for $left {
-                fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-                    write!(f, "Token({})", stringify!($token).chars().nth(0).unwrap())
+            
+            impl<'src> $crate::parsing::Parse<'src> for $id {
+                fn parse(value: &mut $crate::parsing::charstream::CharStream<'src>) -> ::std::result::Result<Self, $crate::parsing::ParseError> {
+                    $crate::parsing::tokens::token(value, $crate::create_tokens!(@text $token), $crate::create_tokens!(@longest $registered $($longest)?)).map(|(span, trivia)| Self { span, trivia })
                 }
-            }
 
-            impl fmt::Display for $left {
-                fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-                    write!(f, "Token({})", stringify!($token).chars().nth(0).unwrap())
+                fn span(&self) -> $crate::parsing::charstream::Span {
+                    self.span
                 }
             }
 
-            #[derive(Clone)]
-            pub struct $right {
-                span: super::Span
-            }
-
-            impl Token for $right {}
-
-            impl Parse for $right {
-                fn parse(value: &mut CharStream) -> Result<Self, ParseError> where Self: Sized {
-                    let chr = stringify!($token).chars().nth(1).unwrap();
-                    let mut token_value = value.clone();
-                    let start = value.position();
-
-                    if let Some(token) = token_value.next() {
-                        if token == chr {
-                            value.goto(token_value.position())?;
-                            let end = value.position();
-                            return Ok(Self { span: super::Span::new(start, end)})
-                        }
-                    }
-
-                    Err(ParseError(format!("could not find right side of: '{}'.", stringify!($token)), value.position()))
-                }
-
-                fn span(&self) -> super::Span {
-                    self.span.clone()
+            impl ::std::fmt::Debug for $id {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    write!(f, "Token({}, at: {})", $crate::create_tokens!(@text $token), self.span.end)
                 }
             }
 
-            impl fmt::Debug for $right {
-                fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-                    write!(f, "Token({})", stringify!($token).chars().nth(1).unwrap())
+            impl ::std::fmt::Display for $id {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    write!(f, "Token({})", $crate::create_tokens!(@text $token))
                 }
             }
 
-            impl fmt::Display for $right {
-                fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-                    write!(f, "Token({})", stringify!($token).chars().nth(1).unwrap())
+            impl $crate::parsing::print::Print for $id {
+                fn print(&self, printer: &mut $crate::parsing::print::Printer) {
+                    printer.leaf(self.trivia, |printer| printer.token($crate::create_tokens!(@text $token)));
                 }
             }
+        )+
+    };
+}
+
+/// Creates the start and end [`Token`] types of every delimiter, and the [`Delimiter`] type that pairs them.
+/// The sides of a delimiter are strings, which can have any length and do not have to be the same length.
+/// ```
+/// # use parseal::{create_delimiters, parsing::{charstream::CharStream, Parse, Group, Identifier, QuotedString}};
+/// create_delimiters! {
+///     "<" ">" LeftAngle RightAngle Angle,
+///     "<!--" "-->" CommentStart CommentEnd Comment,
+///     "begin" "end" Begin End Block
+/// }
+///
+/// # fn main() {
+///     assert!(Group::<Angle, Identifier>::parse(&mut CharStream::new("<T>").build()).is_ok());
+///     assert!(Group::<Block, Identifier>::parse(&mut CharStream::new("begin body end").build()).is_ok());
+///
+///     let comment = QuotedString::<Comment>::parse(&mut CharStream::new("<!-- a -- b -->").build()).unwrap();
+///     assert_eq!(comment.value(), " a -- b ");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! create_delimiters {
+    ($($open:literal $close:literal $left: ident $right: ident $delim:ident),+ $(,)?) => {
+        $(
+            $crate::create_delimiters!(@side $open $left);
+            $crate::create_delimiters!(@side $close $right);
 
             #[derive(Debug, Clone)]
             pub struct $delim {
@@ -161,47 +335,105 @@ macro_rules! create_delimiters {
                 end: $right
             }
 
-            impl Delimiter for $delim {
+            impl $crate::parsing::tokens::Delimiter for $delim {
                 type Start = $left;
                 type End = $right;
 
+                const OPEN: &'static str = $open;
+                const CLOSE: &'static str = $close;
+
                 fn new(start: Self::Start, end: Self::End) -> Self {
                     Self { start, end }
                 }
 
-                fn span(&self) -> super::Span {
-                    super::Span::new(self.start.span().start, self.end.span().end)
+                fn start(&self) -> &Self::Start {
+                    &self.start
+                }
+
+                fn end(&self) -> &Self::End {
+                    &self.end
+                }
+
+                fn span(&self) -> $crate::parsing::charstream::Span {
+                    $crate::parsing::charstream::Span::new($crate::parsing::Parse::span(&self.start).start, $crate::parsing::Parse::span(&self.end).end)
                 }
 
-                fn name() -> String {
-                    String::from(stringify!($delim))
+                fn name() -> ::std::string::String {
+                    ::std::string::String::from(stringify!($delim))
                 }
             }
         )+
     };
+    (@side $text:literal $id:ident) => {
+        #[derive(Clone)]
+        pub struct $id {
+            span: $crate::parsing::charstream::Span,
+            trivia: $crate::parsing::charstream::Trivia
+        }
+
+        impl $crate::parsing::tokens::Token for $id {
+            fn trivia(&self) -> $crate::parsing::charstream::Trivia {
+                self.trivia
+            }
+
+            fn missing(position: $crate::parsing::charstream::Position) -> Self {
+                let span = $crate::parsing::charstream::Span::new(position, position);
+                Self { span, trivia: $crate::parsing::charstream::Trivia::new(span, span) }
+            }
+        }
+
+        impl<'src> $crate::parsing::Parse<'src> for $id {
+            fn parse(value: &mut $crate::parsing::charstream::CharStream<'src>) -> ::std::result::Result<Self, $crate::parsing::ParseError> {
+                $crate::parsing::tokens::token(value, $text, ::std::option::Option::None).map(|(span, trivia)| Self { span, trivia })
+            }
+
+            fn span(&self) -> $crate::parsing::charstream::Span {
+                self.span
+            }
+        }
+
+        impl ::std::fmt::Debug for $id {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                write!(f, "Token({})", $text)
+            }
+        }
+
+        impl ::std::fmt::Display for $id {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                write!(f, "Token({})", $text)
+            }
+        }
+
+        impl $crate::parsing::print::Print for $id {
+            fn print(&self, printer: &mut $crate::parsing::print::Printer) {
+                printer.leaf(self.trivia, |printer| printer.token($text));
+            }
+        }
+    };
 }
 
 create_tokens! {
-    , Comma,
-    . Period,
-    ! Bang,
-    # Hash,
-    _ UnderScore,
-    - Hyphen,
-    + Plus,
-    = Equal,
-    == EqualEqual,
-    : Colon,
-    ; Semicolon,
-    | Pipe,
-    < Less,
-    > Greater,
-    / ForwardSlash
+    , Comma longest,
+    . Period longest,
+    ! Bang longest,
+    # Hash longest,
+    _ UnderScore longest,
+    - Hyphen longest,
+    + Plus longest,
+    = Equal longest,
+    == EqualEqual longest,
+    : Colon longest,
+    ; Semicolon longest,
+    | Pipe longest,
+    < Less longest,
+    > Greater longest,
+    / ForwardSlash longest
 }
 
 create_delimiters! {
-    () LeftParen RightParen Paren,
-    {} LeftBrace RightBrace Brace,
-    [] LeftBracket RightBracket Bracket,
-    "" LeftQuote RightQuote Quote
+    "(" ")" LeftParen RightParen Paren,
+    "{" "}" LeftBrace RightBrace Brace,
+    "[" "]" LeftBracket RightBracket Bracket,
+    "\"" "\"" LeftQuote RightQuote Quote,
+    "'" "'" LeftSingleQuote RightSingleQuote SingleQuote
 }